@@ -0,0 +1,530 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+
+use crate::tiny_diff::join_differences;
+use crate::Difference;
+
+/// The tolerance of an approximate comparison of floating-point numbers.
+///
+/// Two numbers are approximately equal if they are equal, or within any of the configured absolute,
+/// relative or ULP tolerances. Infinities are only equal to infinities of the same sign, and NaN is
+/// only equal to NaN.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{ApproxEq, Tolerance};
+///
+/// let tolerance = Tolerance::absolute(1e-9).with_relative(1e-6);
+/// assert!(0.1_f64 + 0.2 != 0.3);
+/// assert!((0.1_f64 + 0.2).approx_differences(&0.3, &tolerance).is_empty());
+/// assert!(1e10_f64.approx_differences(&(1e10 + 1.0), &tolerance).is_empty());
+/// assert!(!f64::NAN.approx_differences(&1.0, &tolerance).is_empty());
+/// assert!(f64::NAN.approx_differences(&f64::NAN, &tolerance).is_empty());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    absolute: Option<f64>,
+    relative: Option<f64>,
+    ulps: Option<u64>,
+}
+
+impl Tolerance {
+    /// Only accepts equal numbers.
+    pub fn exact() -> Self {
+        Self::default()
+    }
+
+    /// Accepts numbers differing by at most `tolerance`.
+    pub fn absolute(tolerance: f64) -> Self {
+        Self::exact().with_absolute(tolerance)
+    }
+
+    /// Accepts numbers differing by at most `tolerance` times the larger magnitude of both.
+    pub fn relative(tolerance: f64) -> Self {
+        Self::exact().with_relative(tolerance)
+    }
+
+    /// Accepts numbers of the same sign with at most `ulps` representable numbers between them.
+    pub fn ulps(ulps: u64) -> Self {
+        Self::exact().with_ulps(ulps)
+    }
+
+    /// Additionally accepts numbers differing by at most `tolerance`.
+    pub fn with_absolute(self, tolerance: f64) -> Self {
+        Self {
+            absolute: Some(tolerance),
+            ..self
+        }
+    }
+
+    /// Additionally accepts numbers differing by at most `tolerance` times the larger magnitude of both.
+    pub fn with_relative(self, tolerance: f64) -> Self {
+        Self {
+            relative: Some(tolerance),
+            ..self
+        }
+    }
+
+    /// Additionally accepts numbers of the same sign with at most `ulps` representable numbers between them.
+    pub fn with_ulps(self, ulps: u64) -> Self {
+        Self {
+            ulps: Some(ulps),
+            ..self
+        }
+    }
+
+    /// Compares `expected` and `result`, returning the explanation if they are not approximately equal.
+    fn compare(&self, expected: Float, result: Float) -> Option<String> {
+        let (e, r) = (expected.value(), result.value());
+        if e == r || (e.is_nan() && r.is_nan()) {
+            return None;
+        }
+        let mismatch = format!("expected {:?}, got {:?}", expected, result);
+        if !e.is_finite() || !r.is_finite() {
+            return Some(mismatch);
+        }
+        let delta = (e - r).abs();
+        let relative_delta = delta / e.abs().max(r.abs());
+        let ulps = expected.ulps(result);
+        let within = self.absolute.is_some_and(|tolerance| delta <= tolerance)
+            || self
+                .relative
+                .is_some_and(|tolerance| relative_delta <= tolerance)
+            || self
+                .ulps
+                .is_some_and(|tolerance| ulps.is_some_and(|ulps| ulps <= tolerance));
+        if within {
+            return None;
+        }
+        let mut parts = vec![format!("delta {:?}", delta)];
+        if let Some(tolerance) = self.absolute {
+            parts[0] += &format!(" exceeds absolute tolerance {:?}", tolerance);
+        }
+        if let Some(tolerance) = self.relative {
+            parts.push(format!(
+                "relative delta {:?} exceeds relative tolerance {:?}",
+                relative_delta, tolerance
+            ));
+        }
+        if let Some(tolerance) = self.ulps {
+            parts.push(match ulps {
+                Some(ulps) => format!("{} ULPs exceed {} ULPs", ulps, tolerance),
+                None => "signs differ for ULP tolerance".to_owned(),
+            });
+        }
+        Some(format!("{}, {}", mismatch, parts.join(", ")))
+    }
+}
+
+impl Display for Tolerance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(tolerance) = self.absolute {
+            parts.push(format!("±{:?}", tolerance));
+        }
+        if let Some(tolerance) = self.relative {
+            parts.push(format!("relative {:?}", tolerance));
+        }
+        if let Some(ulps) = self.ulps {
+            parts.push(format!("{} ULPs", ulps));
+        }
+        if parts.is_empty() {
+            f.write_str("exactly")
+        } else {
+            f.write_str(&parts.join(" or "))
+        }
+    }
+}
+
+/// An absolute tolerance.
+impl From<f64> for Tolerance {
+    fn from(tolerance: f64) -> Self {
+        Self::absolute(tolerance)
+    }
+}
+
+/// A floating-point number of either precision, so that ULPs are counted in the precision of the values.
+#[derive(Clone, Copy)]
+enum Float {
+    F32(f32),
+    F64(f64),
+}
+
+impl Float {
+    fn value(self) -> f64 {
+        match self {
+            Float::F32(value) => value as f64,
+            Float::F64(value) => value,
+        }
+    }
+
+    /// The number of representable numbers between `self` and `other`, `None` if their signs differ.
+    fn ulps(self, other: Float) -> Option<u64> {
+        match (self, other) {
+            (Float::F32(a), Float::F32(b)) if a.is_sign_negative() == b.is_sign_negative() => {
+                Some((a.to_bits() as i64 - b.to_bits() as i64).unsigned_abs())
+            }
+            (Float::F64(a), Float::F64(b)) if a.is_sign_negative() == b.is_sign_negative() => {
+                Some((a.to_bits() as i128 - b.to_bits() as i128).unsigned_abs() as u64)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Debug for Float {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Float::F32(value) => Debug::fmt(value, f),
+            Float::F64(value) => Debug::fmt(value, f),
+        }
+    }
+}
+
+/// Approximate comparison of an expected value and a result containing floating-point numbers.
+///
+/// Implemented for `f32` and `f64`, exactly compared primitives and strings, and containers and tuples
+/// thereof. Numbers are compared with a [`Tolerance`], the differences name the path of each number
+/// that differs by more than the tolerance, together with the delta and the exceeded tolerance.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{ApproxEq, Difference, Tolerance};
+///
+/// let differences = vec![(1, 0.5), (2, 1.0)]
+///     .approx_differences(&vec![(1, 0.5000001), (2, 1.5)], &Tolerance::absolute(1e-3));
+///
+/// assert_eq!(
+///     differences,
+///     vec![Difference::new(
+///         "[1].1",
+///         "expected 1.0, got 1.5, delta 0.5 exceeds absolute tolerance 0.001"
+///     )]
+/// );
+/// ```
+pub trait ApproxEq: Debug {
+    /// Appends the differences of `self`, the expected value, and `result` exceeding `tolerance`
+    /// at `path` to `differences`.
+    fn approx_diff(
+        &self,
+        result: &Self,
+        tolerance: &Tolerance,
+        path: &str,
+        differences: &mut Vec<Difference>,
+    );
+
+    /// The differences of `self`, the expected value, and `result` exceeding `tolerance`.
+    fn approx_differences(&self, result: &Self, tolerance: &Tolerance) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        self.approx_diff(result, tolerance, "", &mut differences);
+        differences
+    }
+}
+
+/// The assertion comparing the result and expected value approximately with `tolerance`, see [`ApproxEq`].
+///
+/// The reason of a failure lists the differing numbers with their delta and the exceeded tolerance.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{approx_eq, collect_fails, Tolerance};
+///
+/// let fails = collect_fails!(
+///     f64,
+///     Vec<f64>,
+///     Vec<f64>,
+///     vec![(0.1, vec![0.1, 0.2, 0.3]), (0.5, vec![0.5, 1.0, 1.6])].into_iter(),
+///     |input: &f64| vec![*input, input * 2.0, input * 3.0],
+///     approx_eq(Tolerance::relative(1e-9))
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(
+///     fails.fails()[0].reason(),
+///     Some(
+///         "[2]: expected 1.6, got 1.5, delta 0.10000000000000009, \
+///          relative delta 0.06250000000000006 exceeds relative tolerance 1e-9"
+///     )
+/// );
+/// ```
+pub fn approx_eq<T: ApproxEq + ?Sized>(
+    tolerance: impl Into<Tolerance>,
+) -> impl Fn(&T, &T) -> Result<(), String> {
+    let tolerance = tolerance.into();
+    move |result, expected| {
+        let differences = expected.approx_differences(result, &tolerance);
+        if differences.is_empty() {
+            return Ok(());
+        }
+        Err(join_differences(&differences))
+    }
+}
+
+macro_rules! impl_float {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+            impl ApproxEq for $ty {
+                fn approx_diff(
+                    &self,
+                    result: &Self,
+                    tolerance: &Tolerance,
+                    path: &str,
+                    differences: &mut Vec<Difference>,
+                ) {
+                    if let Some(message) = tolerance.compare(Float::$variant(*self), Float::$variant(*result)) {
+                        differences.push(Difference::new(path, message));
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32 => F32, f64 => F64);
+
+macro_rules! impl_exact {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ApproxEq for $ty {
+                fn approx_diff(
+                    &self,
+                    result: &Self,
+                    _: &Tolerance,
+                    path: &str,
+                    differences: &mut Vec<Difference>,
+                ) {
+                    if self != result {
+                        differences.push(Difference::mismatch(path, &self, &result));
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_exact!(
+    (),
+    bool,
+    char,
+    str,
+    String,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+);
+
+impl<T: ApproxEq + ?Sized> ApproxEq for &T {
+    fn approx_diff(
+        &self,
+        result: &Self,
+        tolerance: &Tolerance,
+        path: &str,
+        differences: &mut Vec<Difference>,
+    ) {
+        (**self).approx_diff(*result, tolerance, path, differences)
+    }
+}
+
+impl<T: ApproxEq + ?Sized> ApproxEq for Box<T> {
+    fn approx_diff(
+        &self,
+        result: &Self,
+        tolerance: &Tolerance,
+        path: &str,
+        differences: &mut Vec<Difference>,
+    ) {
+        (**self).approx_diff(result, tolerance, path, differences)
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Option<T> {
+    fn approx_diff(
+        &self,
+        result: &Self,
+        tolerance: &Tolerance,
+        path: &str,
+        differences: &mut Vec<Difference>,
+    ) {
+        match (self, result) {
+            (Some(expected), Some(result)) => {
+                expected.approx_diff(result, tolerance, &format!("{}.0", path), differences)
+            }
+            (None, None) => {}
+            _ => differences.push(Difference::mismatch(path, self, result)),
+        }
+    }
+}
+
+impl<T: ApproxEq, E: ApproxEq> ApproxEq for Result<T, E> {
+    fn approx_diff(
+        &self,
+        result: &Self,
+        tolerance: &Tolerance,
+        path: &str,
+        differences: &mut Vec<Difference>,
+    ) {
+        match (self, result) {
+            (Ok(expected), Ok(result)) => {
+                expected.approx_diff(result, tolerance, &format!("{}.0", path), differences)
+            }
+            (Err(expected), Err(result)) => {
+                expected.approx_diff(result, tolerance, &format!("{}.0", path), differences)
+            }
+            _ => differences.push(Difference::mismatch(path, self, result)),
+        }
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    fn approx_diff(
+        &self,
+        result: &Self,
+        tolerance: &Tolerance,
+        path: &str,
+        differences: &mut Vec<Difference>,
+    ) {
+        for (index, (expected, result)) in self.iter().zip(result).enumerate() {
+            expected.approx_diff(
+                result,
+                tolerance,
+                &format!("{}[{}]", path, index),
+                differences,
+            );
+        }
+        if self.len() != result.len() {
+            differences.push(Difference::new(
+                path,
+                format!("expected {} elements, got {}", self.len(), result.len()),
+            ));
+        }
+    }
+}
+
+impl<T: ApproxEq, const N: usize> ApproxEq for [T; N] {
+    fn approx_diff(
+        &self,
+        result: &Self,
+        tolerance: &Tolerance,
+        path: &str,
+        differences: &mut Vec<Difference>,
+    ) {
+        self[..].approx_diff(&result[..], tolerance, path, differences)
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Vec<T> {
+    fn approx_diff(
+        &self,
+        result: &Self,
+        tolerance: &Tolerance,
+        path: &str,
+        differences: &mut Vec<Difference>,
+    ) {
+        self[..].approx_diff(&result[..], tolerance, path, differences)
+    }
+}
+
+/// Compares the entries of two maps with equal keys, reporting missing and unexpected keys.
+fn approx_entries<'a, K: Debug + 'a, V: ApproxEq + 'a>(
+    expected: impl Iterator<Item = (&'a K, &'a V)>,
+    result: impl Iterator<Item = (&'a K, &'a V)>,
+    get_expected: impl Fn(&K) -> Option<&'a V>,
+    get_result: impl Fn(&K) -> Option<&'a V>,
+    tolerance: &Tolerance,
+    path: &str,
+    differences: &mut Vec<Difference>,
+) {
+    for (key, expected) in expected {
+        let path = format!("{}[{:?}]", path, key);
+        match get_result(key) {
+            Some(result) => expected.approx_diff(result, tolerance, &path, differences),
+            None => differences.push(Difference::new(
+                path,
+                format!("expected {:?}, key is missing", expected),
+            )),
+        }
+    }
+    for (key, result) in result {
+        if get_expected(key).is_none() {
+            differences.push(Difference::new(
+                format!("{}[{:?}]", path, key),
+                format!("unexpected key, got {:?}", result),
+            ));
+        }
+    }
+}
+
+impl<K: Ord + Debug, V: ApproxEq> ApproxEq for BTreeMap<K, V> {
+    fn approx_diff(
+        &self,
+        result: &Self,
+        tolerance: &Tolerance,
+        path: &str,
+        differences: &mut Vec<Difference>,
+    ) {
+        approx_entries(
+            self.iter(),
+            result.iter(),
+            |key| self.get(key),
+            |key| result.get(key),
+            tolerance,
+            path,
+            differences,
+        )
+    }
+}
+
+impl<K: Eq + Hash + Debug, V: ApproxEq, S: std::hash::BuildHasher> ApproxEq for HashMap<K, V, S> {
+    fn approx_diff(
+        &self,
+        result: &Self,
+        tolerance: &Tolerance,
+        path: &str,
+        differences: &mut Vec<Difference>,
+    ) {
+        approx_entries(
+            self.iter(),
+            result.iter(),
+            |key| self.get(key),
+            |key| result.get(key),
+            tolerance,
+            path,
+            differences,
+        )
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name: ApproxEq),+> ApproxEq for ($($name,)+) {
+            fn approx_diff(
+                &self,
+                result: &Self,
+                tolerance: &Tolerance,
+                path: &str,
+                differences: &mut Vec<Difference>,
+            ) {
+                $(self.$index.approx_diff(&result.$index, tolerance, &format!("{}.{}", path, $index), differences);)+
+            }
+        }
+    };
+}
+
+impl_tuple!(A 0);
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
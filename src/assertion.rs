@@ -0,0 +1,110 @@
+use std::fmt::Display;
+
+use crate::TinyDiff;
+
+/// The verdict of an [`Assertion`], `Err` with an optional reason if the assertion failed.
+pub type Verdict = Result<(), Option<String>>;
+
+/// Decides whether the result of a test-case satisfies its expected value.
+///
+/// Implemented for closures `FnMut(&R, &E) -> O` returning an [`AssertOutput`], such as `bool`,
+/// `Result<(), String>` or `Option<String>`, [`Equal`] and [`TinyEqual`]. The reason of a failed assertion
+/// is included in the report.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::TestTable;
+///
+/// let fails = TestTable::new(vec![(2, 1..5), (3, 4..6), (0, 1..2)])
+///     .run(|input| input + 2)
+///     .assert_with(|output, expected| {
+///         if expected.contains(output) {
+///             Ok(())
+///         } else {
+///             Err(format!("{} is not in {:?}", output, expected))
+///         }
+///     })
+///     .collect();
+///
+/// assert_eq!(fails.fails()[0].reason(), Some("2 is not in 1..2"));
+/// assert!(fails.to_string().contains("\treason: 2 is not in 1..2\n"));
+/// ```
+pub trait Assertion<R, E> {
+    /// Returns `Ok` if `result` satisfies `expected`, otherwise `Err` with an optional reason.
+    fn assert(&mut self, result: &R, expected: &E) -> Verdict;
+
+    /// Returns `Ok` if a panic of the test function with `message` satisfies `expected`.
+    ///
+    /// By default a panic fails the test-case, see [`Matches`](crate::matchers::Matches) for expected panics.
+    fn assert_panic(&mut self, message: &str, expected: &E) -> Verdict {
+        let _ = (message, expected);
+        Err(None)
+    }
+}
+
+impl<R, E, F, O> Assertion<R, E> for F
+where
+    F: FnMut(&R, &E) -> O,
+    O: AssertOutput,
+{
+    fn assert(&mut self, result: &R, expected: &E) -> Verdict {
+        self(result, expected).into_verdict()
+    }
+}
+
+/// The return value of an assertion closure, see [`Assertion`].
+pub trait AssertOutput {
+    /// Converts the return value into a [`Verdict`].
+    fn into_verdict(self) -> Verdict;
+}
+
+/// `true` if the assertion holds.
+impl AssertOutput for bool {
+    fn into_verdict(self) -> Verdict {
+        if self {
+            Ok(())
+        } else {
+            Err(None)
+        }
+    }
+}
+
+/// `Err` with the reason if the assertion failed.
+impl<M: Display> AssertOutput for Result<(), M> {
+    fn into_verdict(self) -> Verdict {
+        self.map_err(|reason| Some(reason.to_string()))
+    }
+}
+
+/// `Some` reason if the assertion failed.
+impl<M: Display> AssertOutput for Option<M> {
+    fn into_verdict(self) -> Verdict {
+        match self {
+            None => Ok(()),
+            Some(reason) => Err(Some(reason.to_string())),
+        }
+    }
+}
+
+/// The default assertion, compares the result and expected value for equality.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Equal;
+
+impl<R: PartialEq<E>, E> Assertion<R, E> for Equal {
+    fn assert(&mut self, result: &R, expected: &E) -> Verdict {
+        (result == expected).into_verdict()
+    }
+}
+
+/// Compares the result and expected value by their [`TinyDiff`] differences, holding if there are none.
+///
+/// Unlike [`Equal`], fields excluded from the comparison with `#[tiny(ignore)]` or compared with
+/// `#[tiny(compare_with = "..")]` decide the verdict as they do the report.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TinyEqual;
+
+impl<T: TinyDiff> Assertion<T, T> for TinyEqual {
+    fn assert(&mut self, result: &T, expected: &T) -> Verdict {
+        expected.differences(result).is_empty().into_verdict()
+    }
+}
@@ -0,0 +1,236 @@
+use std::borrow::Cow;
+use std::fmt::{self, Display};
+
+/// The location of a test-case in a source file, displayed as `file:line:column`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Location {
+    file: Cow<'static, str>,
+    line: u32,
+    column: Option<u32>,
+}
+
+impl Location {
+    /// Creates a location of a line in `file`.
+    pub fn new(file: impl Into<Cow<'static, str>>, line: u32) -> Self {
+        Self {
+            file: file.into(),
+            line,
+            column: None,
+        }
+    }
+
+    /// Sets the column of the location.
+    pub fn with_column(mut self, column: u32) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// The location of the caller of a `#[track_caller]` function.
+    #[track_caller]
+    pub fn caller() -> Self {
+        let caller = std::panic::Location::caller();
+        Self::new(caller.file(), caller.line()).with_column(caller.column())
+    }
+
+    /// The path of the source file.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// The 1-based line in the source file.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// The 1-based column in the line, if known.
+    pub fn column(&self) -> Option<u32> {
+        self.column
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)?;
+        if let Some(column) = self.column {
+            write!(f, ":{}", column)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single test-case, the input for the test function and the expected value.
+///
+/// Optionally carries a name and the source location, identifying the case in the report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case<I, E> {
+    name: Option<String>,
+    location: Option<Location>,
+    input: I,
+    expected: E,
+}
+
+impl<I, E> Case<I, E> {
+    /// Creates an unnamed test-case.
+    pub fn new(input: I, expected: E) -> Self {
+        Self {
+            name: None,
+            location: None,
+            input,
+            expected,
+        }
+    }
+
+    /// Creates a test-case identified by `name` in the report.
+    pub fn named(name: impl Into<String>, input: I, expected: E) -> Self {
+        Self::new(input, expected).with_name(name)
+    }
+
+    /// Sets the name identifying the test-case in the report.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the location of the test-case in its source file.
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// The name of the test-case, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The location of the test-case in its source file, if known.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// The input passed to the test function.
+    pub fn input(&self) -> &I {
+        &self.input
+    }
+
+    /// The expected value of the test-case.
+    pub fn expected(&self) -> &E {
+        &self.expected
+    }
+
+    /// Decomposes the test-case into its `(name, location, input, expected)`.
+    pub fn into_parts(self) -> (Option<String>, Option<Location>, I, E) {
+        (self.name, self.location, self.input, self.expected)
+    }
+}
+
+/// Conversion into a [`Case`].
+///
+/// Implemented for `(input, expected)` and `(name, input, expected)` tuples.
+pub trait IntoCase<I, E> {
+    /// Converts `self` into a test-case.
+    fn into_case(self) -> Case<I, E>;
+}
+
+impl<I, E> IntoCase<I, E> for Case<I, E> {
+    fn into_case(self) -> Case<I, E> {
+        self
+    }
+}
+
+impl<I, E> IntoCase<I, E> for (I, E) {
+    fn into_case(self) -> Case<I, E> {
+        Case::new(self.0, self.1)
+    }
+}
+
+impl<I, E> IntoCase<I, E> for (&str, I, E) {
+    fn into_case(self) -> Case<I, E> {
+        Case::named(self.0, self.1, self.2)
+    }
+}
+
+/// Declares a [`Case`], optionally named, at the current source location.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{case, TestTable};
+///
+/// let fails = TestTable::new(vec![
+///     case!("empty path", "" => 0),
+///     case!("trailing slash", "a/" => 2),
+///     case!("a/b" => 2),
+/// ])
+/// .run(|input| input.split('/').filter(|part| !part.is_empty()).count())
+/// .collect();
+///
+/// let location = fails.fails()[0].location().unwrap();
+/// assert_eq!((location.line(), location.column()), (line!() - 7, Some(5)));
+/// ```
+#[macro_export]
+macro_rules! case {
+    ($name:expr, $input:expr => $expected:expr $(,)?) => {
+        $crate::Case::named($name, $input, $expected)
+            .with_location($crate::Location::new(file!(), line!()).with_column(column!()))
+    };
+    ($input:expr => $expected:expr $(,)?) => {
+        $crate::Case::new($input, $expected)
+            .with_location($crate::Location::new(file!(), line!()).with_column(column!()))
+    };
+}
+
+/// Creates a [`Pattern`](crate::matchers::Pattern) matcher of the values matching a pattern with an optional guard,
+/// like [`matches!`]. The report prints the source text of the pattern as the expected value.
+///
+/// The guard cannot capture variables. Its bindings may require the type of the matched values to be
+/// known, as in the `[input => expected]` test-cases of [`collect_fails!`](crate::collect_fails).
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{matches, Pattern};
+/// use tiny_test::{collect_fails, pattern};
+///
+/// #[derive(Debug)]
+/// enum Fragment<'a> {
+///     Separator,
+///     Plain(&'a str),
+/// }
+///
+/// fn parse_fragment(input: &str) -> Result<(&str, Fragment<'_>), ()> {
+///     match input.find('/') {
+///         Some(0) => Ok((input.trim_start_matches('/'), Fragment::Separator)),
+///         Some(end) => Ok((&input[end..], Fragment::Plain(&input[..end]))),
+///         None if input.is_empty() => Err(()),
+///         None => Ok(("", Fragment::Plain(input))),
+///     }
+/// }
+///
+/// let fails = collect_fails!(
+///     &str,
+///     Pattern<Result<(&str, Fragment<'_>), ()>>,
+///     Result<(&str, Fragment<'_>), ()>,
+///     [
+///         "///" => pattern!(Ok(("", Fragment::Separator))),
+///         "path/to" => pattern!(Ok((rest, Fragment::Plain(_))) if rest.starts_with('/')),
+///         "" => pattern!(Err(_)),
+///         "/file" => pattern!(Ok((_, Fragment::Plain(_)))),
+///     ],
+///     |input| parse_fragment(input),
+///     matches
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(format!("{:?}", fails.fails()[0].expected()), "Ok((_, Fragment::Plain(_)))");
+/// assert_eq!(
+///     fails.fails()[0].reason(),
+///     Some("Ok((\"file\", Separator)) does not match `Ok((_, Fragment::Plain(_)))`")
+/// );
+/// ```
+#[macro_export]
+macro_rules! pattern {
+    ($pattern:pat $(if $guard:expr)? $(,)?) => {
+        $crate::matchers::Pattern::new(
+            concat!(stringify!($pattern) $(, " if ", stringify!($guard))?),
+            |value| matches!(value, $pattern $(if $guard)?),
+        )
+    };
+}
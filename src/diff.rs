@@ -0,0 +1,174 @@
+use std::fmt::{self, Write};
+
+/// The number of unchanged lines shown around each change.
+const CONTEXT: usize = 3;
+
+/// The maximum number of cells of the subsequence table, above which the changed lines are
+/// shown as a block of removed lines followed by a block of added lines.
+const MAX_TABLE_CELLS: usize = 1 << 22;
+
+/// A line of a line-by-line diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Line<'a> {
+    /// The line is present in both texts.
+    Same(&'a str),
+    /// The line is only present in the expected text.
+    Removed(&'a str),
+    /// The line is only present in the result text.
+    Added(&'a str),
+}
+
+impl Line<'_> {
+    fn is_same(&self) -> bool {
+        matches!(self, Line::Same(_))
+    }
+}
+
+/// Computes the line-by-line diff of `expected` and `result` using their longest common subsequence.
+///
+/// If the changed lines are too many to compute their subsequence in bounded memory, all of them
+/// are reported as removed and added.
+pub(crate) fn diff_lines<'a>(expected: &'a str, result: &'a str) -> Vec<Line<'a>> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let result: Vec<&str> = result.lines().collect();
+    // the common prefix and suffix do not contribute to the subsequence table
+    let prefix = expected
+        .iter()
+        .zip(&result)
+        .take_while(|(e, r)| e == r)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(result[prefix..].iter().rev())
+        .take_while(|(e, r)| e == r)
+        .count();
+    let removed = &expected[prefix..expected.len() - suffix];
+    let added = &result[prefix..result.len() - suffix];
+
+    let mut lines: Vec<Line<'a>> = expected[..prefix]
+        .iter()
+        .map(|line| Line::Same(line))
+        .collect();
+    let cells = (removed.len() + 1).saturating_mul(added.len() + 1);
+    let (i, j) = if cells > MAX_TABLE_CELLS {
+        (0, 0)
+    } else {
+        common_subsequence(removed, added, &mut lines)
+    };
+    lines.extend(removed[i..].iter().map(|line| Line::Removed(line)));
+    lines.extend(added[j..].iter().map(|line| Line::Added(line)));
+    lines.extend(
+        expected[expected.len() - suffix..]
+            .iter()
+            .map(|line| Line::Same(line)),
+    );
+    lines
+}
+
+/// Pushes the diff of `removed` and `added` along their longest common subsequence to `lines`,
+/// until either is exhausted, returning the number of lines of each consumed.
+fn common_subsequence<'a>(
+    removed: &[&'a str],
+    added: &[&'a str],
+    lines: &mut Vec<Line<'a>>,
+) -> (usize, usize) {
+    // lcs[i][j] is the length of the longest common subsequence of removed[i..] and added[j..]
+    let width = added.len() + 1;
+    let mut lcs = vec![0u32; (removed.len() + 1) * width];
+    for i in (0..removed.len()).rev() {
+        for j in (0..added.len()).rev() {
+            lcs[i * width + j] = if removed[i] == added[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < removed.len() && j < added.len() {
+        if removed[i] == added[j] {
+            lines.push(Line::Same(removed[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            lines.push(Line::Removed(removed[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(added[j]));
+            j += 1;
+        }
+    }
+    (i, j)
+}
+
+/// Writes the unified diff of `expected` and `result`, each line prefixed with `indent`.
+///
+/// Changed lines are marked with `-` for `expected` and `+` for `result`, surrounded by
+/// up to three unchanged lines. Omitted unchanged lines are replaced by a hunk header
+/// `@@ -{expected line},{count} +{result line},{count} @@`.
+pub(crate) fn write_unified(
+    f: &mut impl Write,
+    indent: &str,
+    expected: &str,
+    result: &str,
+) -> fmt::Result {
+    let lines = diff_lines(expected, result);
+    // the visible lines are changes and unchanged lines within the context of a change
+    let mut visible = vec![false; lines.len()];
+    for (index, _) in lines.iter().enumerate().filter(|(_, line)| !line.is_same()) {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + CONTEXT + 1).min(lines.len());
+        visible[start..end].iter_mut().for_each(|v| *v = true);
+    }
+    let all_visible = visible.iter().all(|v| *v);
+
+    let (mut expected_line, mut result_line) = (1, 1);
+    let mut index = 0;
+    while index < lines.len() {
+        if !visible[index] {
+            match lines[index] {
+                Line::Same(_) => {
+                    expected_line += 1;
+                    result_line += 1;
+                }
+                Line::Removed(_) => expected_line += 1,
+                Line::Added(_) => result_line += 1,
+            }
+            index += 1;
+            continue;
+        }
+        let end = visible[index..]
+            .iter()
+            .position(|v| !v)
+            .map_or(lines.len(), |len| index + len);
+        let hunk = &lines[index..end];
+        if !all_visible {
+            let expected_count = hunk
+                .iter()
+                .filter(|line| !matches!(line, Line::Added(_)))
+                .count();
+            let result_count = hunk
+                .iter()
+                .filter(|line| !matches!(line, Line::Removed(_)))
+                .count();
+            writeln!(
+                f,
+                "{}@@ -{},{} +{},{} @@",
+                indent, expected_line, expected_count, result_line, result_count
+            )?;
+            expected_line += expected_count;
+            result_line += result_count;
+        }
+        for line in hunk {
+            match line {
+                Line::Same(line) => writeln!(f, "{}  {}", indent, line)?,
+                Line::Removed(line) => writeln!(f, "{}- {}", indent, line)?,
+                Line::Added(line) => writeln!(f, "{}+ {}", indent, line)?,
+            }
+        }
+        index = end;
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::fmt::Debug;
+
+use crate::{AssertOutput, FailReport, IntoCase, TestTable};
+
+/// Extension trait running iterators of test-cases, see [`IntoCase`].
+///
+/// Shorthand for a [`TestTable`] created from the iterator.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::CollectFails;
+///
+/// let fails = ["1", "2", "x"]
+///     .into_iter()
+///     .zip([Ok(1), Ok(2), Ok(3)])
+///     .collect_fails(|input| input.parse::<u32>().map_err(|_| ()));
+/// assert_eq!(fails.len(), 1);
+///
+/// let fails = (0..4)
+///     .map(|input| (input, input..input + 2))
+///     .collect_fails_by(|input| input + 1, |output, expected| expected.contains(output));
+/// assert!(fails.is_empty());
+/// ```
+pub trait CollectFails<I, E>: Iterator + Sized
+where
+    Self::Item: IntoCase<I, E>,
+{
+    /// Runs the test function for all test-cases, comparing the results to the expected values for equality.
+    fn collect_fails<R, T>(self, test: T) -> FailReport<I, E, R>
+    where
+        T: FnMut(&I) -> R,
+        R: PartialEq<E> + Debug,
+        I: Debug,
+        E: Debug,
+    {
+        TestTable::new(self).run(test).collect()
+    }
+
+    /// Runs the test function for all test-cases, checking the results with a custom assertion.
+    fn collect_fails_by<R, T, A, O>(self, test: T, assert: A) -> FailReport<I, E, R>
+    where
+        T: FnMut(&I) -> R,
+        A: FnMut(&R, &E) -> O,
+        O: AssertOutput,
+        R: Debug,
+        I: Debug,
+        E: Debug,
+    {
+        TestTable::new(self).run(test).assert_with(assert).collect()
+    }
+}
+
+impl<I, E, C> CollectFails<I, E> for C
+where
+    C: Iterator,
+    C::Item: IntoCase<I, E>,
+{
+}
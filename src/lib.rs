@@ -1,116 +1,242 @@
-use std::fmt::Debug;
-use std::fmt::Write;
+mod approx;
+mod assertion;
+mod case;
+mod diff;
+mod iter;
+mod load;
+pub mod matchers;
+mod mode;
+mod report;
+mod table;
+mod tiny_diff;
+mod tree;
+
+pub use approx::{approx_eq, ApproxEq, Tolerance};
+pub use assertion::{AssertOutput, Assertion, Equal, TinyEqual, Verdict};
+pub use case::{Case, IntoCase, Location};
+pub use iter::CollectFails;
+#[cfg(feature = "json")]
+pub use load::cases_from_json;
+#[cfg(feature = "ron")]
+pub use load::cases_from_ron;
+#[cfg(feature = "toml")]
+pub use load::cases_from_toml;
+#[cfg(feature = "csv")]
+pub use load::{cases_from_csv, cases_from_csv_with};
+pub use load::{cases_from_dir, cases_from_markdown, ExpectedFile, LoadError};
+pub use mode::{FailMode, ParseFailModeError};
+pub use report::{check_fails, report_fails, CaseFailure, FailReport, Outcome};
+pub use table::{TestRun, TestTable};
+pub use tiny_diff::{Difference, TinyDiff};
+#[cfg(feature = "derive")]
+pub use tiny_test_derive::TinyDiff;
+
+#[doc(hidden)]
+pub mod __private {
+    //! Support of [`collect_fails!`](crate::collect_fails), not public API.
+    pub use crate::matchers::__private::*;
+    pub use crate::tiny_diff::__private::*;
+}
 
 /// Executes a series of test-cases, collecting error information.
 ///
+/// Shorthand for a [`TestTable`] with explicit input, expected and result types.
+///
 /// # Usage
-/// - An iterator of input and expected output data is required.
-/// - By default compares the result and expected result for equality,
-///   a custom assertion function may be provided as sixth parameter.
-/// - While debugging, panics on assertion failure, otherwise collects all failed data in a `Vec`
+/// - An iterator of input and expected output data is required, optionally named
+///   in the format `(name, input, expected)`, see [`IntoCase`]. Alternatively the test-cases are
+///   listed as `[input => expected, ...]`, checked against the declared types, as required by [`pattern!`].
+///   Listed test-cases have no source location, test-cases declared with [`case!`] record theirs.
+///   Large tables may be loaded from data files, such as `cases_from_json(path)?` with the `json`
+///   feature or `cases_from_csv(path)?` with the `csv` feature.
+/// - By default compares the result and expected result for equality, or by their differences
+///   if they implement [`TinyDiff`], see [`TinyEqual`]. A custom assertion function may be provided
+///   as sixth parameter. The assertion returns a `bool`, or a `Result<(), String>` or `Option<String>`
+///   explaining the failure in the report, see [`Assertion`].
+/// - By default collects all failed data in a [`FailReport`], the [`FailMode`] may be selected
+///   with a leading `mode = FailMode::PanicFirst;` or the `TINY_TEST_MODE` environment variable.
+/// - When the expected value and result are of the same type implementing [`TinyDiff`], the report
+///   lists their differences, otherwise the differences of their parsed `Debug` representations.
+/// - A panic inside the test function is caught and recorded as a failure of its test-case,
+///   the remaining test-cases are still run. Unless the expected value is a matcher accepting the
+///   panic, such as [`Panics`](matchers::Panics) or [`PanicsWith`](matchers::PanicsWith).
 ///
 /// # Examples
 /// **Basic usage:**
 /// ```rust
-/// #[test]
-/// fn test_parse_fragment_any() {
-///     report_fails(collect_fails!(
-///         // input type
-///         &str,
-///         // output type
-///         IResult<&str, Fragment, ()>,
-///         // test cases in format (input, expected)  
-///         vec![
-///             ("/", Ok(("", Fragment::Separator))),
-///             ("///", Ok(("", Fragment::Separator))),
-///             ("path/to/file", Ok(("/to/file", Fragment::Plain("path"))))
-///         ].into_iter(),
-///         // test function
-///         parse_fragment
-///     ));
+/// use tiny_test::{collect_fails, report_fails};
+///
+/// fn parse_digit(input: &str) -> Result<u32, ()> {
+///     input.parse().map_err(|_| ())
 /// }
+///
+/// report_fails(collect_fails!(
+///     // input type
+///     &str,
+///     // output type
+///     Result<u32, ()>,
+///     // test cases in format (input, expected)
+///     vec![
+///         ("0", Ok(0)),
+///         ("42", Ok(42)),
+///         ("x", Err(()))
+///     ].into_iter(),
+///     // test function
+///     parse_digit
+/// ));
 /// ```
 ///
-/// **Custom assertion:**
+/// **Listed test-cases:**
 /// ```rust
-/// fn test_in_range() {
-///     report_fails(collect_fails!(
-///         usize,
-///         std::ops::Range<usize>,
-///         usize,
-///         vec![(2, 1..4), (3, 4..6), (0, 1..3)].into_iter(),
-///         |input| input + 2,
-///         |output: &usize, expected: &Range<usize>| expected.contains(output)
-///     ));
-/// }
+/// use tiny_test::{collect_fails, report_fails};
+///
+/// report_fails(collect_fails!(
+///     &str,
+///     usize,
+///     ["" => 0, "a b" => 2, " a  b c " => 3],
+///     |input: &&str| input.split_whitespace().count()
+/// ));
 /// ```
-#[macro_export]
-macro_rules! collect_fails {
-    ($input:ty, $expected:ty, $result:ty, $cases:expr, $test:expr, $assert:expr) => {{
-        let mut case_id = 0usize;
-        $cases
-            .filter_map(|(input, expected)| {
-                case_id += 1;
-                let result: $result = $test(&input);
-                let assert = $assert(&result, &expected);
-                debug_assert!(
-                    assert,
-                    "test case {}: assertion failed for input `{:#?}`\n\texpected `{:#?}`\n\tresult `{:#?}`\n",
-                    case_id, &input, &expected, &result
-                );
-                if assert {
-                    None
-                } else {
-                    Some((input, expected, result, case_id))
-                }
-            })
-            .collect::<Vec<($input, $expected, $result, usize)>>()
-    }};
-    ($input:ty, $result:ty, $cases:expr, $test:expr) => {
-        collect_fails!($input, $result, $result, $cases, $test, |e, r| e == r)
-    };
-}
-
-/// Constructs a pretty print report of all failed assertions.
-/// - **This method does not check for plausible input!**
-/// - Panics if `fails.is_empty() == false`.
 ///
-/// # Usage
-/// Usually used in combination with `collect_fails`
+/// **Test-cases with source locations:**
+/// ```rust
+/// use tiny_test::{case, collect_fails};
 ///
-/// **Basic usage:**
+/// let cases = vec![
+///     case!("a b" => 1),
+///     case!("a  b c" => 2),
+/// ];
+/// let fails = collect_fails!(&str, usize, cases, |input: &&str| input.split_whitespace().count());
+///
+/// let lines: Vec<u32> = fails.iter().map(|fail| fail.location().unwrap().line()).collect();
+/// assert_eq!(lines, [line!() - 6, line!() - 5]);
+///
+/// let fails = collect_fails!(&str, usize, ["a b" => 1, "c" => 2], |input: &&str| input.len());
+/// assert!(fails.iter().all(|fail| fail.location().is_none()));
+/// ```
+///
+/// **Panicking test function:**
 /// ```rust
+/// use tiny_test::collect_fails;
 ///
-/// report_fails(vec![
-///     ("input string", "expected string", "", 1),
-///     ("hello world!", "hello papa!", "hello mom!", 2),
-/// ])
+/// let fails = collect_fails!(
+///     &str,
+///     char,
+///     vec![("abc", 'a'), ("", 'x'), ("xyz", 'x')].into_iter(),
+///     |input: &&str| input.chars().next().unwrap()
+/// );
 ///
-/// // One or more assertions failed:
-/// // test case 1: assertion failed for input `"input string"`
-/// //         expected `"expected string"`
-/// //         result `""`
-
-/// // test case 2: assertion failed for input `"hello world!"`
-/// //         expected `"hello papa!"`
-// //         result `"hello mom!"`
+/// assert_eq!(fails.len(), 1);
+/// assert!(fails.fails()[0].panic_message().is_some());
+/// ```
+///
+/// **Custom assertion:**
+/// ```rust
+/// use std::ops::Range;
+/// use tiny_test::{collect_fails, report_fails};
+///
+/// report_fails(collect_fails!(
+///     usize,
+///     Range<usize>,
+///     usize,
+///     vec![(2, 1..5), (3, 4..6), (0, 1..3)].into_iter(),
+///     |input| input + 2,
+///     |output: &usize, expected: &Range<usize>| expected.contains(output)
+/// ));
+/// ```
 ///
+/// **Expected panics:**
+/// ```rust
+/// use tiny_test::collect_fails;
+/// use tiny_test::matchers::{matches, ok, eq, Matcher, PanicsWith};
+///
+/// fn nth_digit(input: &(&str, usize)) -> Result<u32, String> {
+///     let (digits, index) = input;
+///     let c = digits.as_bytes()[*index] as char;
+///     c.to_digit(10).ok_or_else(|| format!("{:?} is not a digit", c))
+/// }
+///
+/// let fails = collect_fails!(
+///     (&str, usize),
+///     Box<dyn Matcher<Result<u32, String>>>,
+///     Result<u32, String>,
+///     vec![
+///         (("123", 1), ok(eq(2)).boxed()),
+///         (("123", 3), PanicsWith("index out of bounds").boxed()),
+///         (("123", 0), PanicsWith("index out of bounds").boxed()),
+///     ]
+///     .into_iter(),
+///     nth_digit,
+///     matches
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(fails.fails()[0].reason(), Some("expected panic, returned Ok(1)"));
 /// ```
-pub fn report_fails<I: Debug, E: Debug, R: Debug>(fails: Vec<(I, E, R, usize)>) {
-    if fails.is_empty() {
-        return;
-    }
-    let mut report = String::with_capacity(1024);
-    for (input, expected, result, case_id) in fails {
-        if writeln!(
-                &mut report,
-                "test case {}: assertion failed for input `{:#?}`\n\texpected `{:#?}`\n\tresult `{:#?}`\n",
-                case_id, input, expected, result
-            )
-            .is_err()
-            {
-                report += &format!("test case {}: assertion failed, unable to print message\n\n", case_id);
-            };
-    }
-    panic!("One or more assertions failed:\n{}", report);
+#[macro_export]
+macro_rules! collect_fails {
+    (@run [$($mode:expr)?]; $input:ty, $result:ty, [$($rows:tt)*], $test:expr) => {
+        $crate::collect_fails!(@run [$($mode)?]; $input, $result, $result, [$($rows)*], $test, |result: &$result, expected: &$result| {
+            #[allow(unused_imports)]
+            use $crate::__private::{ViaPartialEq, ViaTinyDiff};
+            (&$crate::__private::DiffProbe(expected, result)).tiny_equal()
+        })
+    };
+    (@run [$($mode:expr)?]; $input:ty, $expected:ty, $result:ty, [$($rows:tt)*], $test:expr, $assert:expr) => {
+        $crate::collect_fails!(
+            @collect [$($mode)?]; $input, $expected, $result,
+            ($crate::collect_fails!(@table $input, $expected, [$($rows)*])),
+            $test, $assert
+        )
+    };
+    (@run [$($mode:expr)?]; $input:ty, $expected:ty, $result:ty, $cases:expr, $test:expr, $assert:expr) => {
+        $crate::collect_fails!(
+            @collect [$($mode)?]; $input, $expected, $result,
+            ($crate::TestTable::<$input, $expected>::new($cases)),
+            $test, $assert
+        )
+    };
+    (@run [$($mode:expr)?]; $input:ty, $result:ty, $cases:expr, $test:expr) => {
+        $crate::collect_fails!(@run [$($mode)?]; $input, $result, $result, $cases, $test, |result: &$result, expected: &$result| {
+            #[allow(unused_imports)]
+            use $crate::__private::{ViaPartialEq, ViaTinyDiff};
+            (&$crate::__private::DiffProbe(expected, result)).tiny_equal()
+        })
+    };
+    (@table $input:ty, $expected:ty, [$($case_input:expr => $case_expected:expr),* $(,)?]) => {
+        // the rows have no source location, the location of a call generated here would be that of
+        // the macro invocation rather than of the row
+        $crate::TestTable::<$input, $expected>::new(::std::vec::Vec::<$crate::Case<$input, $expected>>::from([
+            $($crate::Case::new($case_input, $case_expected)),*
+        ]))
+    };
+    (@table $input:ty, $expected:ty, $cases:expr) => {
+        $crate::TestTable::<$input, $expected>::new($cases)
+    };
+    (@collect [$($mode:expr)?]; $input:ty, $expected:ty, $result:ty, $table:expr, $test:expr, $assert:expr) => {
+        $table
+            $(.mode($mode))?
+            .run(|input| -> $result { $test(input) })
+            .assert_by($crate::__private::assertion::<$result, $expected, _, _, _>(
+                |result, expected| $assert(result, expected),
+                |message, expected| {
+                    #[allow(unused_imports)]
+                    use $crate::__private::{ViaMatcher, ViaPanicked};
+                    (&$crate::__private::PanicProbe::<$result, $expected>(expected, ::std::marker::PhantomData))
+                        .check_panic(message)
+                },
+            ))
+            .diff_with(|expected, result| {
+                #[allow(unused_imports)]
+                use $crate::__private::{ViaDebug, ViaTinyDiff};
+                (&$crate::__private::DiffProbe(expected, result)).tiny_differences()
+            })
+            .collect()
+    };
+    (mode = $mode:expr; $($args:tt)*) => {
+        $crate::collect_fails!(@run [$mode]; $($args)*)
+    };
+    ($($args:tt)*) => {
+        $crate::collect_fails!(@run []; $($args)*)
+    };
 }
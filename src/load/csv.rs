@@ -0,0 +1,205 @@
+//! Test-cases from CSV and TSV files.
+
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::{read, LoadError};
+use crate::{Case, Location};
+
+/// Loads the test-cases of the CSV or TSV file at `path`, parsing the input and expected values by `FromStr`.
+///
+/// See [`cases_from_csv_with`].
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{cases_from_csv, collect_fails};
+///
+/// let path = std::env::temp_dir().join("tiny_test_cases_from_csv.csv");
+/// std::fs::write(
+///     &path,
+///     "name,input,expected,comment\n\
+///      empty,,0,\n\
+///      ,a b,2,\"two words, one space\"\n\
+///      tabs,\"a\tb\tc\",2,\n",
+/// )
+/// .unwrap();
+///
+/// let fails = collect_fails!(
+///     String,
+///     usize,
+///     cases_from_csv(&path).unwrap(),
+///     |input: &String| input.split_whitespace().count()
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(fails.fails()[0].name(), Some("tabs"));
+/// assert_eq!(fails.fails()[0].location().unwrap().line(), 4);
+///
+/// std::fs::write(&path, "input,expected\na,1\nb,x\n").unwrap();
+/// let err = cases_from_csv::<String, usize>(&path).unwrap_err();
+/// assert!(err.to_string().ends_with(
+///     ":3: row 2: expected: cannot parse \"x\": invalid digit found in string"
+/// ));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn cases_from_csv<I, E>(path: impl AsRef<Path>) -> Result<Vec<Case<I, E>>, LoadError>
+where
+    I: FromStr,
+    I::Err: Display,
+    E: FromStr,
+    E::Err: Display,
+{
+    cases_from_csv_with(path, str::parse, str::parse)
+}
+
+/// Loads the test-cases of the CSV or TSV file at `path`, parsing the input and expected columns with the
+/// given functions.
+///
+/// The first line of the file names the columns. The `input` and `expected` columns are required, the
+/// `name` column names the test-cases, other columns, such as comments, are ignored. Files with the `.tsv`
+/// extension are separated by tabs, others by commas. Fields containing the separator, quotes or line
+/// breaks are quoted, with quotes written twice. A leading UTF-8 byte order mark is skipped. The
+/// test-cases are located at the line of their row, identifying them in the report.
+///
+/// # Errors
+/// Fails if the file is unreadable, is not valid CSV, lacks a required column, or a field is not parsed,
+/// naming the file, line and row.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{cases_from_csv_with, collect_fails};
+///
+/// let path = std::env::temp_dir().join("tiny_test_cases_from_csv_with.tsv");
+/// std::fs::write(&path, "input\texpected\n1 2 3\t6\n4 -5\t-1\n").unwrap();
+///
+/// let cases = cases_from_csv_with(
+///     &path,
+///     |text| text.split(' ').map(str::parse).collect::<Result<Vec<i32>, _>>(),
+///     str::parse,
+/// )
+/// .unwrap();
+///
+/// let fails = collect_fails!(Vec<i32>, i32, cases, |input: &Vec<i32>| input.iter().sum());
+///
+/// assert!(fails.is_empty());
+///
+/// std::fs::write(&path, "\u{feff}input\texpected\n1\t1\n").unwrap();
+/// let cases = cases_from_csv_with(&path, str::parse::<i32>, str::parse::<i32>).unwrap();
+/// assert_eq!(cases.len(), 1);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn cases_from_csv_with<I, E, PI, PE, EI, EE>(
+    path: impl AsRef<Path>,
+    input: PI,
+    expected: PE,
+) -> Result<Vec<Case<I, E>>, LoadError>
+where
+    PI: Fn(&str) -> Result<I, EI>,
+    PE: Fn(&str) -> Result<E, EE>,
+    EI: Display,
+    EE: Display,
+{
+    let path = path.as_ref();
+    let (file, text) = read(path)?;
+    // spreadsheets commonly save CSV files with a byte order mark, which is not part of the header
+    let text = text.strip_prefix('\u{feff}').unwrap_or(&text);
+    let separator = match path.extension() {
+        Some(extension) if extension.eq_ignore_ascii_case("tsv") => '\t',
+        _ => ',',
+    };
+    let mut records = records(text, separator)
+        .map_err(|(line, message)| LoadError::new(file.as_str(), message).with_line(line))?
+        .into_iter();
+    let Some((header_line, header)) = records.next() else {
+        return Err(LoadError::new(
+            file,
+            "missing the header naming the columns",
+        ));
+    };
+    let column = |name: &str| header.iter().position(|column| column == name);
+    let (Some(input_column), Some(expected_column)) = (column("input"), column("expected")) else {
+        return Err(LoadError::new(
+            file,
+            "expected the columns \"input\" and \"expected\" in the header",
+        )
+        .with_line(header_line));
+    };
+    let name_column = column("name");
+    records
+        .enumerate()
+        .map(|(index, (line, fields))| {
+            let error = |message: String| {
+                LoadError::new(file.as_str(), message)
+                    .with_line(line)
+                    .with_row(index + 1)
+            };
+            if fields.len() != header.len() {
+                return Err(error(format!(
+                    "expected {} fields, got {}",
+                    header.len(),
+                    fields.len()
+                )));
+            }
+            let parse = |column: &str, text: &str, err: &dyn Display| {
+                error(format!("{}: cannot parse {:?}: {}", column, text, err))
+            };
+            let input_text = &fields[input_column];
+            let input_value = input(input_text).map_err(|err| parse("input", input_text, &err))?;
+            let expected_text = &fields[expected_column];
+            let expected_value =
+                expected(expected_text).map_err(|err| parse("expected", expected_text, &err))?;
+            let case = Case::new(input_value, expected_value)
+                .with_location(Location::new(file.clone(), line));
+            Ok(match name_column.map(|column| &fields[column]) {
+                Some(name) if !name.is_empty() => case.with_name(name.as_str()),
+                _ => case,
+            })
+        })
+        .collect()
+}
+
+/// A record of a CSV file, the line it starts at and its fields.
+type Record = (u32, Vec<String>);
+
+/// Splits `text` into records of fields, each with the line it starts at, skipping blank lines.
+///
+/// `Err` with the line and explanation of an unterminated quoted field.
+fn records(text: &str, separator: char) -> Result<Vec<Record>, (u32, String)> {
+    let mut records = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    while chars.peek().is_some() {
+        let start = line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        loop {
+            match chars.next() {
+                None if quoted => return Err((start, "unterminated quoted field".to_owned())),
+                Some('"') if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                Some('"') if quoted => quoted = false,
+                Some('"') if field.is_empty() => quoted = true,
+                Some('\n') if quoted => {
+                    line += 1;
+                    field.push('\n');
+                }
+                Some('\r') if !quoted && chars.peek() == Some(&'\n') => {}
+                None | Some('\n') => {
+                    line += 1;
+                    fields.push(field);
+                    break;
+                }
+                Some(c) if c == separator && !quoted => fields.push(std::mem::take(&mut field)),
+                Some(c) => field.push(c),
+            }
+        }
+        if fields.len() > 1 || !fields[0].is_empty() {
+            records.push((start, fields));
+        }
+    }
+    Ok(records)
+}
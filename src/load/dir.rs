@@ -0,0 +1,203 @@
+//! Test-cases from a directory of fixture files.
+
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::{read, LoadError};
+use crate::matchers::{displays, Matcher};
+use crate::{Case, Location};
+
+/// Loads a test-case of each file in the directory `dir` whose name matches `pattern`, expecting the
+/// content of the file with the same stem and the `expected` extension.
+///
+/// The `pattern` matches file names with the wildcards `*` and `?`, such as `*.src`. The input of a
+/// test-case is the content of its file, the test-case is named after the file stem and located at the
+/// file. The expected value is an [`ExpectedFile`] matcher, so a missing or unreadable expected file
+/// fails its test-case rather than skipping it. The test-cases are sorted by file name.
+///
+/// # Errors
+/// Fails if the directory or an input file is unreadable.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::matches;
+/// use tiny_test::{cases_from_dir, collect_fails, ExpectedFile};
+///
+/// let dir = std::env::temp_dir().join("tiny_test_cases_from_dir");
+/// std::fs::create_dir_all(&dir).unwrap();
+/// std::fs::write(dir.join("sum.src"), "1+2").unwrap();
+/// std::fs::write(dir.join("sum.expected"), "1 + 2").unwrap();
+/// std::fs::write(dir.join("product.src"), "3*4").unwrap();
+/// std::fs::write(dir.join("product.expected"), "3 * 4").unwrap();
+/// std::fs::write(dir.join("nested.src"), "(1+2)*3").unwrap();
+/// std::fs::write(dir.join("latin1.src"), "caf\u{e9}").unwrap();
+/// std::fs::write(dir.join("latin1.expected"), b"caf\xe9").unwrap();
+/// std::fs::write(dir.join("notes.txt"), "not a test-case").unwrap();
+///
+/// fn format(source: &str) -> String {
+///     source.replace('+', " + ").replace('*', " * ")
+/// }
+///
+/// let fails = collect_fails!(
+///     String,
+///     ExpectedFile,
+///     String,
+///     cases_from_dir(&dir, "*.src", ".expected").unwrap(),
+///     |source: &String| format(source),
+///     matches
+/// );
+///
+/// assert_eq!(fails.len(), 2);
+/// assert_eq!(fails.fails()[0].name(), Some("latin1"));
+/// assert!(fails.fails()[0].reason().unwrap().starts_with("unreadable expected file"));
+/// assert_eq!(fails.fails()[1].name(), Some("nested"));
+/// assert!(fails.fails()[1].reason().unwrap().starts_with("missing expected file"));
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub fn cases_from_dir(
+    dir: impl AsRef<Path>,
+    pattern: &str,
+    expected: &str,
+) -> Result<Vec<Case<String, ExpectedFile>>, LoadError> {
+    let dir = dir.as_ref();
+    let entries = std::fs::read_dir(dir)
+        .map_err(|err| LoadError::new(dir.display().to_string(), err.to_string()))?;
+    let mut inputs = Vec::new();
+    for entry in entries {
+        let entry =
+            entry.map_err(|err| LoadError::new(dir.display().to_string(), err.to_string()))?;
+        let path = entry.path();
+        let matched = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| glob_match(pattern, name));
+        if matched && path.is_file() {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+    inputs
+        .into_iter()
+        .map(|path| {
+            let (file, input) = read(&path)?;
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let expected_path = path.with_file_name(format!("{}{}", stem, expected));
+            let expected = ExpectedFile {
+                path: expected_path.display().to_string(),
+                content: std::fs::read_to_string(&expected_path).map_err(Arc::new),
+            };
+            Ok(Case::named(stem, input, expected).with_location(Location::new(file, 1)))
+        })
+        .collect()
+}
+
+/// Whether the file `name` matches `pattern`, with the wildcards `*` for any text and `?` for any character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    // the positions after the last `*`, to retry with a longer match of the star
+    let (mut p, mut n, mut star) = (0, 0, None);
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((after, matched)) => {
+                    star = Some((after, matched + 1));
+                    p = after;
+                    n = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Matches text equal to the content of an expected file, failing if the file is missing or unreadable.
+///
+/// A mismatch of multi-line texts is explained by their line-by-line diff, see [`cases_from_dir`].
+#[derive(Clone)]
+pub struct ExpectedFile {
+    path: String,
+    content: Result<String, Arc<io::Error>>,
+}
+
+impl ExpectedFile {
+    /// The path of the expected file.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The content of the expected file, `None` if it is missing or unreadable.
+    pub fn content(&self) -> Option<&str> {
+        self.content.as_deref().ok()
+    }
+
+    /// The error reading the expected file, `None` if it was read.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.content.as_ref().err().map(|err| err.as_ref())
+    }
+
+    /// Explains why the expected file has no content, telling a missing file from an unreadable one.
+    fn unread(&self, err: &io::Error) -> String {
+        match err.kind() {
+            io::ErrorKind::NotFound => format!("missing expected file {}", self.path),
+            _ => format!("unreadable expected file {}: {}", self.path, err),
+        }
+    }
+}
+
+/// Expected files are equal if their paths and contents are, or their errors are of the same kind.
+impl PartialEq for ExpectedFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+            && match (&self.content, &other.content) {
+                (Ok(content), Ok(other)) => content == other,
+                (Err(err), Err(other)) => err.kind() == other.kind(),
+                _ => false,
+            }
+    }
+}
+
+impl Eq for ExpectedFile {}
+
+impl Hash for ExpectedFile {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+        match &self.content {
+            Ok(content) => content.hash(state),
+            Err(err) => err.kind().hash(state),
+        }
+    }
+}
+
+impl Debug for ExpectedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.content {
+            Ok(content) => write!(f, "{:?}", content),
+            Err(err) => f.write_str(&self.unread(err)),
+        }
+    }
+}
+
+impl<T: AsRef<str> + ?Sized> Matcher<T> for ExpectedFile {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        match &self.content {
+            Ok(content) => displays(content.as_str()).check(actual.as_ref()),
+            Err(err) => Err(self.unread(err)),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Test-cases from JSON files.
+
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+
+use super::row::Row;
+use super::{line_columns, read, LoadError};
+use crate::Case;
+
+/// Loads the test-cases of the JSON file at `path`.
+///
+/// The file holds an array of rows, each an object with the fields `input`, `expected` and an optional
+/// `name`. The values are deserialized with `serde`, so a `Result` is written as `{"Ok": value}`. The
+/// test-cases are located at the line of their row, identifying them in the report.
+///
+/// # Errors
+/// Fails if the file is unreadable, is not valid JSON, or a row does not represent a test-case, naming
+/// the file, line, column and row. Rows nesting arrays and objects more than 127 levels deep are
+/// rejected.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{cases_from_json, collect_fails};
+///
+/// let path = std::env::temp_dir().join("tiny_test_cases_from_json.json");
+/// std::fs::write(
+///     &path,
+///     r#"[
+///         {"name": "empty", "input": "", "expected": 0},
+///         {"input": "a b", "expected": 2},
+///         {"input": " a  b c ", "expected": 2}
+///     ]"#,
+/// )
+/// .unwrap();
+///
+/// let fails = collect_fails!(
+///     String,
+///     usize,
+///     cases_from_json(&path).unwrap(),
+///     |input: &String| input.split_whitespace().count()
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// let location = fails.fails()[0].location().unwrap();
+/// assert_eq!((location.file(), location.line()), (path.display().to_string().as_str(), 4));
+///
+/// std::fs::write(&path, r#"[{"input": "a", "expected": 1}, {"input": 2, "expected": 1}]"#).unwrap();
+/// let err = cases_from_json::<String, usize>(&path).unwrap_err();
+/// assert_eq!((err.line(), err.row()), (Some(1), Some(2)));
+/// assert_eq!(err.message(), "invalid type: integer `2`, expected a string");
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn cases_from_json<I: DeserializeOwned, E: DeserializeOwned>(
+    path: impl AsRef<Path>,
+) -> Result<Vec<Case<I, E>>, LoadError> {
+    let (file, text) = read(path.as_ref())?;
+    let rows: Vec<&RawValue> =
+        serde_json::from_str(&text).map_err(|err| error(&file, &text, 0, &err))?;
+    // the rows are slices of the text, their distance from its start is their position
+    let starts: Vec<_> = rows
+        .iter()
+        .map(|row| row.get().as_ptr() as usize - text.as_ptr() as usize)
+        .collect();
+    let positions = line_columns(&text, &starts);
+    rows.iter()
+        .zip(starts.into_iter().zip(positions))
+        .enumerate()
+        .map(|(index, (row, (start, (line, _))))| {
+            serde_json::from_str::<Row<I, E>>(row.get())
+                .map(|row| row.into_case(&file, line))
+                .map_err(|err| error(&file, &text, start, &err).with_row(index + 1))
+        })
+        .collect()
+}
+
+/// The error `err` of `file`, raised reading the part of `text` from the byte position `start`.
+fn error(file: &str, text: &str, start: usize, err: &serde_json::Error) -> LoadError {
+    // the message of `serde_json` ends with its position
+    let message = err.to_string();
+    let position = format!(" at line {} column {}", err.line(), err.column());
+    let error = LoadError::new(file, message.strip_suffix(&position).unwrap_or(&message));
+    if err.line() == 0 {
+        return error;
+    }
+    // the 1-based line and byte column of `serde_json`, relative to `start`
+    let line_start: usize = text[start..]
+        .split_inclusive('\n')
+        .take(err.line() - 1)
+        .map(str::len)
+        .sum();
+    let mut position = (start + line_start + err.column().saturating_sub(1)).min(text.len());
+    while !text.is_char_boundary(position) {
+        position -= 1;
+    }
+    let (line, column) = line_columns(text, &[position])[0];
+    error.with_line(line).with_column(column)
+}
@@ -0,0 +1,202 @@
+//! Test-cases from the examples of Markdown documents.
+
+use std::path::Path;
+
+use super::{read, LoadError};
+use crate::{Case, Location};
+
+/// Loads the test-cases of the Markdown file at `path`, pairs of fenced code blocks tagged `input` and
+/// `expected`, such as ```` ```input ```` and ```` ```expected ````.
+///
+/// Each block tagged `input` is followed by a block tagged `expected`, with only prose in between.
+/// Blocks with other tags are ignored. A test-case is named after the nearest heading above it,
+/// numbered if the heading has multiple test-cases, and located at the line of its input block.
+/// The input and expected values are the contents of the blocks, without the final line break.
+///
+/// # Errors
+/// Fails if the file is unreadable, a block is not closed, or a block is not part of a pair,
+/// naming the file and line.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::display_eq;
+/// use tiny_test::{cases_from_markdown, collect_fails};
+///
+/// let path = std::env::temp_dir().join("tiny_test_cases_from_markdown.md");
+/// std::fs::write(
+///     &path,
+///     "# Greetings\n\
+///      \n\
+///      A name is greeted:\n\
+///      \n\
+///      ```input\n\
+///      world\n\
+///      ```\n\
+///      \n\
+///      ```expected\n\
+///      Hello, world!\n\
+///      ```\n\
+///      \n\
+///      ## Empty names\n\
+///      \n\
+///      ```input\n\
+///      ```\n\
+///      ```expected\n\
+///      Hello, stranger!\n\
+///      ```\n",
+/// )
+/// .unwrap();
+///
+/// let fails = collect_fails!(
+///     String,
+///     String,
+///     String,
+///     cases_from_markdown(&path, "input", "expected").unwrap(),
+///     |name: &String| format!("Hello, {}!", name),
+///     display_eq
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(fails.fails()[0].name(), Some("Empty names"));
+/// assert_eq!(fails.fails()[0].location().unwrap().line(), 15);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn cases_from_markdown(
+    path: impl AsRef<Path>,
+    input: &str,
+    expected: &str,
+) -> Result<Vec<Case<String, String>>, LoadError> {
+    let (file, text) = read(path.as_ref())?;
+    let error = |line: usize, message: String| {
+        LoadError::new(file.as_str(), message).with_line(line as u32)
+    };
+    let unpaired = |line: usize| {
+        error(
+            line,
+            format!(
+                "the `{}` block is not followed by an `{}` block",
+                input, expected
+            ),
+        )
+    };
+    let mut headings = Vec::new();
+    let mut pairs = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line));
+    while let Some((line, text)) = lines.next() {
+        if let Some(title) = heading(text) {
+            headings.push(title);
+            continue;
+        }
+        let Some((indent, fence, tag)) = fence(text) else {
+            continue;
+        };
+        let mut content = Vec::new();
+        loop {
+            let Some((_, text)) = lines.next() else {
+                return Err(error(
+                    line,
+                    format!("the code block `{}` is not closed", fence),
+                ));
+            };
+            if is_closing(text, fence) {
+                break;
+            }
+            let spaces = text.len() - text.trim_start_matches(' ').len();
+            content.push(&text[spaces.min(indent)..]);
+        }
+        let content = content.join("\n");
+        if let Some((line, _)) = pending.as_ref().filter(|_| tag != expected) {
+            return Err(unpaired(*line));
+        }
+        if tag == input {
+            pending = Some((line, content));
+        } else if tag == expected {
+            let Some((input_line, input_text)) = pending.take() else {
+                return Err(error(
+                    line,
+                    format!(
+                        "the `{}` block does not follow an `{}` block",
+                        expected, input
+                    ),
+                ));
+            };
+            pairs.push((
+                headings.len().checked_sub(1),
+                input_line,
+                input_text,
+                content,
+            ));
+        }
+    }
+    if let Some((line, _)) = pending {
+        return Err(unpaired(line));
+    }
+    let count = |heading: Option<usize>| pairs.iter().filter(|pair| pair.0 == heading).count();
+    let mut numbers = vec![0; headings.len()];
+    let cases = pairs
+        .iter()
+        .map(|(heading, line, input, expected)| {
+            let case = Case::new(input.clone(), expected.clone())
+                .with_location(Location::new(file.clone(), *line as u32));
+            let Some(heading) = *heading else {
+                return case;
+            };
+            if count(Some(heading)) == 1 {
+                return case.with_name(headings[heading]);
+            }
+            numbers[heading] += 1;
+            case.with_name(format!("{} ({})", headings[heading], numbers[heading]))
+        })
+        .collect();
+    Ok(cases)
+}
+
+/// The title of an ATX heading line, such as `## Escapes ##`.
+fn heading(line: &str) -> Option<&str> {
+    let line = indented(line)?;
+    let text = line.trim_start_matches('#');
+    let level = line.len() - text.len();
+    if !(1..=6).contains(&level) || !(text.is_empty() || text.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let text = text.trim();
+    let closed = text.trim_end_matches('#');
+    if closed.is_empty() || closed.ends_with([' ', '\t']) {
+        Some(closed.trim_end())
+    } else {
+        Some(text)
+    }
+}
+
+/// The indentation, fence and tag of a line opening a fenced code block, such as ```` ```input ````.
+fn fence(line: &str) -> Option<(usize, &str, &str)> {
+    let text = indented(line)?;
+    let marker = text.chars().next().filter(|&c| c == '`' || c == '~')?;
+    let info = text.trim_start_matches(marker);
+    let fence = &text[..text.len() - info.len()];
+    if fence.len() < 3 || (marker == '`' && info.contains('`')) {
+        return None;
+    }
+    let tag = info.split_whitespace().next().unwrap_or_default();
+    Some((line.len() - text.len(), fence, tag))
+}
+
+/// Whether `line` closes the code block opened by `fence`.
+fn is_closing(line: &str, fence: &str) -> bool {
+    let Some(text) = indented(line) else {
+        return false;
+    };
+    let marker = &fence[..1];
+    let rest = text.trim_start_matches(marker);
+    text.len() - rest.len() >= fence.len() && rest.trim().is_empty()
+}
+
+/// The line without its indentation of up to 3 spaces, `None` if it is indented further.
+fn indented(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches(' ');
+    (line.len() - text.len() <= 3).then_some(text)
+}
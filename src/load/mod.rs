@@ -0,0 +1,144 @@
+//! Loading test-cases from data files.
+
+#[cfg(feature = "csv")]
+mod csv;
+mod dir;
+#[cfg(feature = "json")]
+mod json;
+mod markdown;
+#[cfg(feature = "ron")]
+mod ron;
+#[cfg(any(feature = "json", feature = "ron", feature = "toml"))]
+mod row;
+#[cfg(feature = "toml")]
+mod toml;
+
+use std::fmt::{self, Display};
+use std::path::Path;
+
+#[cfg(feature = "csv")]
+pub use self::csv::{cases_from_csv, cases_from_csv_with};
+pub use self::dir::{cases_from_dir, ExpectedFile};
+#[cfg(feature = "json")]
+pub use self::json::cases_from_json;
+pub use self::markdown::cases_from_markdown;
+#[cfg(feature = "ron")]
+pub use self::ron::cases_from_ron;
+#[cfg(feature = "toml")]
+pub use self::toml::cases_from_toml;
+
+/// The error loading test-cases from a file, with the position of the offending text or row.
+///
+/// Displayed as `file:line:column: row 3: reason`, omitting the unknown parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadError {
+    file: String,
+    line: Option<u32>,
+    column: Option<u32>,
+    row: Option<usize>,
+    message: String,
+}
+
+impl LoadError {
+    /// Creates the error of `file`, explained by `message`.
+    pub fn new(file: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            file: file.into(),
+            line: None,
+            column: None,
+            row: None,
+            message: message.into(),
+        }
+    }
+
+    /// Sets the 1-based line of the error in the file.
+    pub fn with_line(mut self, line: u32) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Sets the 1-based column of the error in its line.
+    pub fn with_column(mut self, column: u32) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Sets the 1-based number of the row of the invalid test-case.
+    pub fn with_row(mut self, row: usize) -> Self {
+        self.row = Some(row);
+        self
+    }
+
+    /// The path of the file.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// The 1-based line of the error in the file, if known.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// The 1-based column of the error in its line, if known.
+    pub fn column(&self) -> Option<u32> {
+        self.column
+    }
+
+    /// The 1-based number of the row of the invalid test-case, if any.
+    pub fn row(&self) -> Option<usize> {
+        self.row
+    }
+
+    /// The explanation of the error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.file)?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+        if let Some(row) = self.row {
+            write!(f, ": row {}", row)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Reads the file at `path`, returning its displayed path and content.
+fn read(path: &Path) -> Result<(String, String), LoadError> {
+    let file = path.display().to_string();
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok((file, text)),
+        Err(err) => Err(LoadError::new(file, err.to_string())),
+    }
+}
+
+/// The 1-based lines and columns of the ascending byte positions `starts` in `text`, in one pass.
+#[cfg(any(feature = "json", feature = "ron", feature = "toml"))]
+fn line_columns(text: &str, starts: &[usize]) -> Vec<(u32, u32)> {
+    let (mut line, mut column, mut scanned) = (1, 1, 0);
+    starts
+        .iter()
+        .map(|&start| {
+            let skipped = &text[scanned..start];
+            match skipped.rfind('\n') {
+                Some(end) => {
+                    line += skipped.matches('\n').count();
+                    column = skipped[end + 1..].chars().count() + 1;
+                }
+                None => column += skipped.chars().count(),
+            }
+            scanned = start;
+            (line as u32, column as u32)
+        })
+        .collect()
+}
@@ -0,0 +1,210 @@
+//! Test-cases from RON files.
+
+use std::path::Path;
+
+use ron::extensions::Extensions;
+use ron::Options;
+use serde::de::DeserializeOwned;
+
+use super::row::Row;
+use super::{line_columns, read, LoadError};
+use crate::Case;
+
+/// Loads the test-cases of the RON file at `path`.
+///
+/// The file holds a list of rows, each a struct `(input: .., expected: .., name: ..)` with an optional
+/// name. The values are deserialized with `serde`, so expectations are written like Rust values, such as
+/// `Ok(("", Separator))` of a `Result<(String, Fragment), String>`, and options are implicit, so a name
+/// is written `name: "empty"` rather than `name: Some("empty")`. The test-cases are located at the line
+/// of their row, identifying them in the report.
+///
+/// # Errors
+/// Fails if the file is unreadable, is not valid RON, or a row does not represent a test-case, naming
+/// the file, line, column and row.
+///
+/// # Examples
+/// ```rust
+/// use serde::Deserialize;
+/// use tiny_test::{cases_from_ron, collect_fails};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// enum Fragment {
+///     Separator,
+///     Plain(String),
+/// }
+///
+/// fn parse_fragment(input: &str) -> Result<(String, Fragment), String> {
+///     match input.find('/') {
+///         Some(0) => Ok((input.trim_start_matches('/').to_owned(), Fragment::Separator)),
+///         Some(end) => Ok((input[end..].to_owned(), Fragment::Plain(input[..end].to_owned()))),
+///         None if input.is_empty() => Err("empty input".to_owned()),
+///         None => Ok((String::new(), Fragment::Plain(input.to_owned()))),
+///     }
+/// }
+///
+/// let path = std::env::temp_dir().join("tiny_test_cases_from_ron.ron");
+/// std::fs::write(
+///     &path,
+///     r#"[
+///         // separators are merged
+///         (name: "separator", input: "///", expected: Ok(("", Separator))),
+///         (input: "path/to", expected: Ok(("/to", Plain("path")))),
+///         (name: "empty", input: "", expected: Err("no input")),
+///     ]"#,
+/// )
+/// .unwrap();
+///
+/// let fails = collect_fails!(
+///     String,
+///     Result<(String, Fragment), String>,
+///     cases_from_ron(&path).unwrap(),
+///     |input: &String| parse_fragment(input)
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(fails.fails()[0].name(), Some("empty"));
+/// assert_eq!(fails.fails()[0].location().unwrap().line(), 5);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn cases_from_ron<I: DeserializeOwned, E: DeserializeOwned>(
+    path: impl AsRef<Path>,
+) -> Result<Vec<Case<I, E>>, LoadError> {
+    let (file, text) = read(path.as_ref())?;
+    let options = Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
+    let positions = line_columns(&text, &row_starts(&text));
+    let rows: Vec<Row<I, E>> = options.from_str(&text).map_err(|err| {
+        let (line, column) = (err.position.line as u32, err.position.col as u32);
+        let error = LoadError::new(file.as_str(), err.code.to_string())
+            .with_line(line)
+            .with_column(column);
+        // the row containing the error is the last one starting before it
+        let row = positions
+            .iter()
+            .take_while(|&&position| position <= (line, column))
+            .count();
+        match row {
+            0 => error,
+            row => error.with_row(row),
+        }
+    })?;
+    let cases = rows
+        .into_iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let line = positions.get(index).map_or(1, |&(line, _)| line);
+            row.into_case(&file, line)
+        })
+        .collect();
+    Ok(cases)
+}
+
+/// The byte positions of the rows of the top-level list of a RON document.
+///
+/// Strings, characters and comments are skipped, so their brackets and commas do not delimit rows.
+fn row_starts(text: &str) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut depth = 0usize;
+    // whether the next value of the top-level list starts a row
+    let mut expect_row = false;
+    let mut chars = text.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        let rest = &text[position..];
+        if c.is_whitespace() {
+            continue;
+        }
+        if rest.starts_with("//") {
+            while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+            continue;
+        }
+        if rest.starts_with("/*") {
+            // block comments nest
+            chars.next();
+            let mut nesting = 1;
+            while nesting > 0 {
+                let Some((position, _)) = chars.next() else {
+                    break;
+                };
+                if text[position..].starts_with("/*") {
+                    chars.next();
+                    nesting += 1;
+                } else if text[position..].starts_with("*/") {
+                    chars.next();
+                    nesting -= 1;
+                }
+            }
+            continue;
+        }
+        if depth == 0 && rest.starts_with("#![") {
+            // an attribute such as `#![enable(implicit_some)]`
+            while chars.next_if(|&(_, c)| c != ']').is_some() {}
+            chars.next();
+            continue;
+        }
+        if depth == 1 && expect_row && c != ']' {
+            starts.push(position);
+            expect_row = false;
+        }
+        match c {
+            '"' => skip_string(&mut chars, 0),
+            'r' | 'b' if raw_string_hashes(rest).is_some() => {
+                let hashes = raw_string_hashes(rest).expect("the raw string is checked");
+                // skip the rest of the prefix and the opening quote
+                for _ in 0..rest.find('"').expect("a raw string has a quote") {
+                    chars.next();
+                }
+                skip_string(&mut chars, hashes);
+            }
+            '\'' => {
+                if let Some((_, '\\')) = chars.next() {
+                    chars.next();
+                }
+                while chars.next_if(|&(_, c)| c != '\'').is_some() {}
+                chars.next();
+            }
+            '(' | '[' | '{' => {
+                depth += 1;
+                expect_row = depth == 1;
+            }
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 1 => expect_row = true,
+            // the rest of an identifier, so an `r` or `b` inside it does not start a raw string
+            c if c.is_alphanumeric() || c == '_' => {
+                while chars
+                    .next_if(|&(_, c)| c.is_alphanumeric() || c == '_')
+                    .is_some()
+                {}
+            }
+            _ => {}
+        }
+    }
+    starts
+}
+
+/// The number of `#` of a raw string such as `r#"..."#` or `br"..."` at the start of `text`, `None` if
+/// `text` does not start with a raw string.
+fn raw_string_hashes(text: &str) -> Option<usize> {
+    let rest = text.strip_prefix('b').unwrap_or(text).strip_prefix('r')?;
+    let hashes = rest.len() - rest.trim_start_matches('#').len();
+    rest[hashes..].starts_with('"').then_some(hashes)
+}
+
+/// Skips the rest of a string after its opening quote, closed by a quote and `hashes` times `#`.
+fn skip_string(chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>, hashes: usize) {
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\\' if hashes == 0 => {
+                chars.next();
+            }
+            '"' => {
+                let mut closing = 0;
+                while closing < hashes && chars.next_if(|&(_, c)| c == '#').is_some() {
+                    closing += 1;
+                }
+                if closing == hashes {
+                    return;
+                }
+            }
+            _ => {}
+        }
+    }
+}
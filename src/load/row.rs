@@ -0,0 +1,26 @@
+//! The rows of case files read with `serde`.
+
+use serde::Deserialize;
+
+use crate::{Case, Location};
+
+/// A test-case of a case file, with the fields `input`, `expected` and an optional `name`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Row<I, E> {
+    name: Option<String>,
+    input: I,
+    expected: E,
+}
+
+impl<I, E> Row<I, E> {
+    /// Converts the row into a test-case located at `line` of `file`.
+    pub(super) fn into_case(self, file: &str, line: u32) -> Case<I, E> {
+        let case = Case::new(self.input, self.expected)
+            .with_location(Location::new(file.to_owned(), line));
+        match self.name {
+            Some(name) => case.with_name(name),
+            None => case,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Test-cases from TOML files.
+
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use toml::Spanned;
+
+use super::row::Row;
+use super::{line_columns, read, LoadError};
+use crate::Case;
+
+/// Loads the test-cases of the TOML file at `path`.
+///
+/// Each test-case is a `[[case]]` table with the keys `input`, `expected` and an optional `name`. The
+/// values are deserialized with `serde`, so a `Result` is written as the table `{ Ok = value }`. The
+/// test-cases are located at the line of their `[[case]]` header, identifying them in the report.
+///
+/// # Errors
+/// Fails if the file is unreadable, is not valid TOML, or a table does not represent a test-case, naming
+/// the file, line and column.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{cases_from_toml, collect_fails};
+///
+/// let path = std::env::temp_dir().join("tiny_test_cases_from_toml.toml");
+/// std::fs::write(
+///     &path,
+///     r#"
+/// [[case]]
+/// name = "decimal"
+/// input = "42"
+/// expected = { Ok = 42 }
+///
+/// [[case]]
+/// input = "-7"
+/// expected.Err = "invalid digit found in string"
+///
+/// [[case]]
+/// input = "1_000"
+/// expected = { Ok = 1_000 }
+/// "#,
+/// )
+/// .unwrap();
+///
+/// let fails = collect_fails!(
+///     String,
+///     Result<u32, String>,
+///     cases_from_toml(&path).unwrap(),
+///     |input: &String| input.parse::<u32>().map_err(|err| err.to_string())
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(fails.fails()[0].location().unwrap().line(), 11);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn cases_from_toml<I: DeserializeOwned, E: DeserializeOwned>(
+    path: impl AsRef<Path>,
+) -> Result<Vec<Case<I, E>>, LoadError> {
+    let (file, text) = read(path.as_ref())?;
+    let document: Document<I, E> = toml::from_str(&text).map_err(|err| {
+        let error = LoadError::new(file.as_str(), err.message());
+        match err.span() {
+            Some(span) => {
+                let (line, column) = line_columns(&text, &[span.start])[0];
+                error.with_line(line).with_column(column)
+            }
+            None => error,
+        }
+    })?;
+    let starts: Vec<_> = document.case.iter().map(|row| row.span().start).collect();
+    let cases = document
+        .case
+        .into_iter()
+        .zip(line_columns(&text, &starts))
+        .map(|(row, (line, _))| row.into_inner().into_case(&file, line))
+        .collect();
+    Ok(cases)
+}
+
+/// A TOML case file, the test-cases are its `[[case]]` tables.
+#[derive(Deserialize)]
+struct Document<I, E> {
+    case: Vec<Spanned<Row<I, E>>>,
+}
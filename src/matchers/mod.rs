@@ -0,0 +1,924 @@
+//! Composable matchers usable as the expected value of a test-case.
+//!
+//! A [`Matcher`] decides whether a result is acceptable and describes the acceptable results with its
+//! `Debug` representation, which the report prints as the expectation. Matchers are checked by the
+//! [`matches()`] assertion.
+//!
+//! # Examples
+//! ```rust
+//! use tiny_test::matchers::{all_of, eq, in_range, matches, not, ok, Matcher};
+//! use tiny_test::TestTable;
+//!
+//! let fails = TestTable::new(vec![
+//!     ("1", ok(in_range(0..4)).boxed()),
+//!     ("2", ok(all_of((in_range(1..4), not(eq(2))))).boxed()),
+//!     ("x", ok(eq(3)).boxed()),
+//! ])
+//! .run(|input| input.parse::<u32>().map_err(|err| err.to_string()))
+//! .assert_with(matches)
+//! .collect();
+//!
+//! assert_eq!(fails.len(), 2);
+//! assert_eq!(fails.fails()[0].reason(), Some("2 is equal to 2"));
+//! assert!(fails.to_string().contains("\texpected `Ok(all of [in range 1..4, not equal to 2])`\n"));
+//! assert_eq!(
+//!     fails.fails()[1].reason(),
+//!     Some("expected Ok(equal to 3), got Err(\"invalid digit found in string\")")
+//! );
+//! ```
+
+mod regex;
+mod structure;
+mod text;
+mod unordered;
+
+use std::fmt::{self, Debug};
+use std::ops::RangeBounds;
+
+use self::regex::Regex;
+pub use self::structure::{debug_pattern, fields, DebugPattern, Fields};
+pub use self::text::{debug_eq, debugs, display_eq, displays, Debugged, Displayed, Text};
+pub use self::unordered::{unordered, unordered_set, Items, Unordered};
+use crate::tiny_diff::join_differences;
+use crate::{ApproxEq, Assertion, Tolerance, Verdict};
+
+/// A predicate on the result of a test-case, describing the accepted results by its `Debug` representation.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{contains, Matcher};
+///
+/// let matcher = contains("world");
+/// assert_eq!(format!("{:?}", matcher), "containing \"world\"");
+/// assert_eq!(matcher.check("hello world"), Ok(()));
+/// assert_eq!(
+///     matcher.check("hello mom"),
+///     Err("\"hello mom\" does not contain \"world\"".to_owned())
+/// );
+/// ```
+pub trait Matcher<T: ?Sized>: Debug {
+    /// Returns `Ok` if `actual` is accepted, otherwise `Err` with the reason.
+    fn check(&self, actual: &T) -> Result<(), String>;
+
+    /// Returns `Ok` if a panic with `message` is accepted instead of a value, otherwise `Err` with the reason.
+    ///
+    /// Only [`Panics`] and [`PanicsWith`] accept panics.
+    fn check_panic(&self, message: &str) -> Result<(), String> {
+        let _ = message;
+        Err("unexpected panic".to_owned())
+    }
+
+    /// Boxes the matcher, allowing different matchers in the expected values of one table.
+    fn boxed<'a>(self) -> Box<dyn Matcher<T> + 'a>
+    where
+        Self: Sized + 'a,
+    {
+        Box::new(self)
+    }
+}
+
+impl<T: ?Sized, M: Matcher<T> + ?Sized> Matcher<T> for &M {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        (**self).check(actual)
+    }
+
+    fn check_panic(&self, message: &str) -> Result<(), String> {
+        (**self).check_panic(message)
+    }
+}
+
+impl<T: ?Sized, M: Matcher<T> + ?Sized> Matcher<T> for Box<M> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        (**self).check(actual)
+    }
+
+    fn check_panic(&self, message: &str) -> Result<(), String> {
+        (**self).check_panic(message)
+    }
+}
+
+/// The assertion of tables whose expected values are [`Matcher`]s, pass it to
+/// [`assert_with`](crate::TestRun::assert_with) or as the assertion of [`collect_fails!`](crate::collect_fails).
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{in_range, matches, InRange};
+/// use tiny_test::{collect_fails, report_fails};
+/// use std::ops::Range;
+///
+/// report_fails(collect_fails!(
+///     usize,
+///     InRange<Range<usize>>,
+///     usize,
+///     vec![(2, in_range(1..5)), (3, in_range(4..6))].into_iter(),
+///     |input| input + 2,
+///     matches
+/// ));
+/// ```
+pub fn matches<T: ?Sized, M: Matcher<T> + ?Sized>(actual: &T, matcher: &M) -> Result<(), String> {
+    matcher.check(actual)
+}
+
+/// The [`Assertion`] of tables whose expected values are [`Matcher`]s, including expected panics,
+/// pass it to [`assert_by`](crate::TestRun::assert_by).
+///
+/// [`collect_fails!`](crate::collect_fails) accepts expected panics of matchers with any assertion.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{eq, Matcher, Matches, PanicsWith};
+/// use tiny_test::TestTable;
+///
+/// let fails = TestTable::new(vec![
+///     (1, eq(20).boxed()),
+///     (7, PanicsWith("out of bounds").boxed()),
+///     (2, PanicsWith("out of bounds").boxed()),
+/// ])
+/// .run(|index| [10, 20, 30][*index])
+/// .assert_by(Matches)
+/// .collect();
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(fails.fails()[0].reason(), Some("expected panic, returned 30"));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Matches;
+
+impl<R, M: Matcher<R>> Assertion<R, M> for Matches {
+    fn assert(&mut self, result: &R, expected: &M) -> Verdict {
+        expected.check(result).map_err(Some)
+    }
+
+    fn assert_panic(&mut self, message: &str, expected: &M) -> Verdict {
+        expected.check_panic(message).map_err(Some)
+    }
+}
+
+/// Matches panics of the test function, see [`Matches`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Panics;
+
+impl Debug for Panics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("panic")
+    }
+}
+
+impl<T: Debug + ?Sized> Matcher<T> for Panics {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        Err(format!("expected panic, returned {:?}", actual))
+    }
+
+    fn check_panic(&self, _: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Matches panics of the test function with a message containing the substring, see [`Matches`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PanicsWith<S>(pub S);
+
+impl<S: Debug> Debug for PanicsWith<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "panic with message containing {:?}", self.0)
+    }
+}
+
+impl<T: Debug + ?Sized, S: AsRef<str> + Debug> Matcher<T> for PanicsWith<S> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        Err(format!("expected panic, returned {:?}", actual))
+    }
+
+    fn check_panic(&self, message: &str) -> Result<(), String> {
+        if message.contains(self.0.as_ref()) {
+            Ok(())
+        } else {
+            Err(format!(
+                "panic message {:?} does not contain {:?}",
+                message,
+                self.0.as_ref()
+            ))
+        }
+    }
+}
+
+/// Matches any value.
+pub fn any() -> Anything {
+    Anything
+}
+
+/// See [`any`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Anything;
+
+impl Debug for Anything {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("anything")
+    }
+}
+
+impl<T: ?Sized> Matcher<T> for Anything {
+    fn check(&self, _: &T) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Matches values equal to `expected`.
+pub fn eq<V>(expected: V) -> EqualTo<V> {
+    EqualTo(expected)
+}
+
+/// See [`eq`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EqualTo<V>(V);
+
+impl<V: Debug> Debug for EqualTo<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "equal to {:?}", self.0)
+    }
+}
+
+impl<T: PartialEq<V> + Debug + ?Sized, V: Debug> Matcher<T> for EqualTo<V> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        if *actual == self.0 {
+            Ok(())
+        } else {
+            Err(format!("{:?} is not equal to {:?}", actual, self.0))
+        }
+    }
+}
+
+/// Matches values within `range`.
+pub fn in_range<R>(range: R) -> InRange<R> {
+    InRange(range)
+}
+
+/// See [`in_range`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct InRange<R>(R);
+
+impl<R: Debug> Debug for InRange<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "in range {:?}", self.0)
+    }
+}
+
+impl<T: PartialOrd + Debug, R: RangeBounds<T> + Debug> Matcher<T> for InRange<R> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        if self.0.contains(actual) {
+            Ok(())
+        } else {
+            Err(format!("{:?} is not in range {:?}", actual, self.0))
+        }
+    }
+}
+
+/// Matches values approximately equal to `expected`, containing floating-point numbers compared
+/// with `tolerance`, see [`ApproxEq`]. A number as tolerance is an absolute tolerance.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{approx, Matcher};
+/// use tiny_test::Tolerance;
+///
+/// assert_eq!(approx(0.3, 1e-9).check(&(0.1 + 0.2)), Ok(()));
+/// assert_eq!(
+///     format!("{:?}", approx(vec![1.0, 2.0], Tolerance::ulps(4))),
+///     "approximately [1.0, 2.0] within 4 ULPs"
+/// );
+/// ```
+pub fn approx<T>(expected: T, tolerance: impl Into<Tolerance>) -> Approx<T> {
+    Approx {
+        expected,
+        tolerance: tolerance.into(),
+    }
+}
+
+/// See [`approx`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct Approx<T> {
+    expected: T,
+    tolerance: Tolerance,
+}
+
+impl<T: Debug> Debug for Approx<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "approximately {:?} within {}",
+            self.expected, self.tolerance
+        )
+    }
+}
+
+impl<T: ApproxEq> Matcher<T> for Approx<T> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        let differences = self.expected.approx_differences(actual, &self.tolerance);
+        if differences.is_empty() {
+            return Ok(());
+        }
+        Err(join_differences(&differences))
+    }
+}
+
+/// Matches strings containing the substring `needle`, or collections containing an element equal to `needle`.
+pub fn contains<N>(needle: N) -> Contains<N> {
+    Contains(needle)
+}
+
+/// See [`contains`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Contains<N>(N);
+
+impl<N: Debug> Debug for Contains<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "containing {:?}", self.0)
+    }
+}
+
+impl<N: Debug> Contains<N> {
+    fn verdict(&self, actual: &dyn Debug, contained: bool) -> Result<(), String> {
+        if contained {
+            Ok(())
+        } else {
+            Err(format!("{:?} does not contain {:?}", actual, self.0))
+        }
+    }
+}
+
+impl<N: AsRef<str> + Debug> Matcher<str> for Contains<N> {
+    fn check(&self, actual: &str) -> Result<(), String> {
+        self.verdict(&actual, actual.contains(self.0.as_ref()))
+    }
+}
+
+impl<N: AsRef<str> + Debug> Matcher<&str> for Contains<N> {
+    fn check(&self, actual: &&str) -> Result<(), String> {
+        self.check(*actual)
+    }
+}
+
+impl<N: AsRef<str> + Debug> Matcher<String> for Contains<N> {
+    fn check(&self, actual: &String) -> Result<(), String> {
+        self.check(actual.as_str())
+    }
+}
+
+impl<T: PartialEq<N> + Debug, N: Debug> Matcher<[T]> for Contains<N> {
+    fn check(&self, actual: &[T]) -> Result<(), String> {
+        self.verdict(&actual, actual.iter().any(|item| *item == self.0))
+    }
+}
+
+impl<T: PartialEq<N> + Debug, N: Debug, const LEN: usize> Matcher<[T; LEN]> for Contains<N> {
+    fn check(&self, actual: &[T; LEN]) -> Result<(), String> {
+        self.check(&actual[..])
+    }
+}
+
+impl<T: PartialEq<N> + Debug, N: Debug> Matcher<Vec<T>> for Contains<N> {
+    fn check(&self, actual: &Vec<T>) -> Result<(), String> {
+        self.check(&actual[..])
+    }
+}
+
+/// Matches strings in which the regular expression `pattern` matches.
+///
+/// Supports literals, `.`, character classes such as `[a-z_]` and `[^0-9]`, the escapes `\d`, `\w`, `\s`
+/// and their negations, anchors `^` and `$`, groups with alternatives `(a|b)`, and the greedy and lazy
+/// (`?` suffixed) quantifiers `*`, `+`, `?` and `{n,m}`.
+///
+/// # Panics
+/// Panics if `pattern` is not a valid regular expression.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{matches_regex, Matcher};
+///
+/// let matcher = matches_regex(r"^v\d+\.\d+(\.\d+)?$");
+/// assert_eq!(matcher.check("v1.2"), Ok(()));
+/// assert_eq!(matcher.check("v1.2.3"), Ok(()));
+/// assert!(matcher.check("v1.x").is_err());
+/// ```
+#[track_caller]
+pub fn matches_regex(pattern: &str) -> MatchesRegex {
+    match Regex::new(pattern) {
+        Ok(regex) => MatchesRegex(regex),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// See [`matches_regex`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct MatchesRegex(Regex);
+
+impl Debug for MatchesRegex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "matching regex {:?}", self.0.as_str())
+    }
+}
+
+impl Matcher<str> for MatchesRegex {
+    fn check(&self, actual: &str) -> Result<(), String> {
+        if self.0.is_match(actual) {
+            Ok(())
+        } else {
+            Err(format!(
+                "{:?} does not match regex {:?}",
+                actual,
+                self.0.as_str()
+            ))
+        }
+    }
+}
+
+impl Matcher<&str> for MatchesRegex {
+    fn check(&self, actual: &&str) -> Result<(), String> {
+        self.check(*actual)
+    }
+}
+
+impl Matcher<String> for MatchesRegex {
+    fn check(&self, actual: &String) -> Result<(), String> {
+        self.check(actual.as_str())
+    }
+}
+
+/// Matches `Ok` results whose value is matched by `matcher`.
+pub fn ok<M>(matcher: M) -> IsOk<M> {
+    IsOk(matcher)
+}
+
+/// See [`ok`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IsOk<M>(M);
+
+impl<M: Debug> Debug for IsOk<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ok({:?})", self.0)
+    }
+}
+
+impl<T: Debug, E: Debug, M: Matcher<T>> Matcher<Result<T, E>> for IsOk<M> {
+    fn check(&self, actual: &Result<T, E>) -> Result<(), String> {
+        match actual {
+            Ok(value) => self.0.check(value),
+            Err(_) => Err(format!("expected {:?}, got {:?}", self, actual)),
+        }
+    }
+}
+
+/// Matches `Err` results whose error is matched by `matcher`.
+pub fn err<M>(matcher: M) -> IsErr<M> {
+    IsErr(matcher)
+}
+
+/// See [`err`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IsErr<M>(M);
+
+impl<M: Debug> Debug for IsErr<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Err({:?})", self.0)
+    }
+}
+
+impl<T: Debug, E: Debug, M: Matcher<E>> Matcher<Result<T, E>> for IsErr<M> {
+    fn check(&self, actual: &Result<T, E>) -> Result<(), String> {
+        match actual {
+            Err(error) => self.0.check(error),
+            Ok(_) => Err(format!("expected {:?}, got {:?}", self, actual)),
+        }
+    }
+}
+
+/// A `Result` is a matcher of results, comparing the value of `Ok` for equality and matching
+/// the error of `Err` with a [`Matcher`], such as [`any`] or [`predicate`].
+///
+/// The error type of the test function does not need to implement `PartialEq`, [`ExpectResult`] is the
+/// expected type of tables with different error matchers.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{any, matches, predicate, ExpectResult, Matcher};
+/// use tiny_test::collect_fails;
+///
+/// #[derive(Debug)]
+/// enum ParseError {
+///     Empty,
+///     InvalidDigit(usize),
+/// }
+///
+/// fn parse(input: &str) -> Result<u32, ParseError> {
+///     if input.is_empty() {
+///         return Err(ParseError::Empty);
+///     }
+///     input.chars().enumerate().try_fold(0, |value, (index, c)| {
+///         c.to_digit(10)
+///             .map(|digit| value * 10 + digit)
+///             .ok_or(ParseError::InvalidDigit(index))
+///     })
+/// }
+///
+/// let invalid_digit = predicate("an invalid digit", |err: &ParseError| {
+///     matches!(err, ParseError::InvalidDigit(_))
+/// });
+/// let fails = collect_fails!(
+///     &str,
+///     ExpectResult<u32, ParseError>,
+///     Result<u32, ParseError>,
+///     vec![
+///         ("12", Ok(12)),
+///         ("", Err(any().boxed())),
+///         ("1x", Err(invalid_digit.boxed())),
+///         ("4y", Ok(4)),
+///         ("7", Err(any().boxed())),
+///     ]
+///     .into_iter(),
+///     |input: &&str| parse(input),
+///     matches
+/// );
+///
+/// assert_eq!(fails.len(), 2);
+/// assert_eq!(fails.fails()[0].reason(), Some("expected Ok(4), got Err(InvalidDigit(1))"));
+/// assert_eq!(fails.fails()[1].reason(), Some("expected Err(anything), got Ok(7)"));
+/// ```
+impl<T, V, E, M> Matcher<Result<T, E>> for Result<V, M>
+where
+    T: PartialEq<V> + Debug,
+    V: Debug,
+    E: Debug,
+    M: Matcher<E>,
+{
+    fn check(&self, actual: &Result<T, E>) -> Result<(), String> {
+        match (self, actual) {
+            (Ok(expected), Ok(value)) if *value == *expected => Ok(()),
+            (Err(matcher), Err(error)) => matcher.check(error).map_err(|reason| {
+                format!("expected Err({:?}), got {:?}: {}", matcher, actual, reason)
+            }),
+            (Ok(expected), _) => Err(format!("expected Ok({:?}), got {:?}", expected, actual)),
+            (Err(matcher), Ok(_)) => Err(format!("expected Err({:?}), got {:?}", matcher, actual)),
+        }
+    }
+}
+
+/// The expected value of a test function returning `Result<T, E>`, either `Ok` with the value or
+/// `Err` with a matcher of the error.
+pub type ExpectResult<T, E> = Result<T, Box<dyn Matcher<E>>>;
+
+/// Matches values matching a pattern, see [`pattern!`](crate::pattern).
+pub struct Pattern<T: ?Sized> {
+    source: &'static str,
+    matches: fn(&T) -> bool,
+}
+
+impl<T: ?Sized> Pattern<T> {
+    /// Creates a matcher of the values accepted by `matches`, described by the pattern `source`.
+    pub fn new(source: &'static str, matches: fn(&T) -> bool) -> Self {
+        Self { source, matches }
+    }
+
+    /// The source text of the pattern.
+    pub fn source(&self) -> &'static str {
+        self.source
+    }
+}
+
+impl<T: ?Sized> Clone for Pattern<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Pattern<T> {}
+
+impl<T: ?Sized> Debug for Pattern<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.source)
+    }
+}
+
+impl<T: Debug + ?Sized> Matcher<T> for Pattern<T> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        if (self.matches)(actual) {
+            Ok(())
+        } else {
+            Err(format!("{:?} does not match `{}`", actual, self.source))
+        }
+    }
+}
+
+/// Matches values accepted by `predicate`, described by `description`.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{predicate, Matcher};
+///
+/// let even = predicate("even", |value: &u32| value % 2 == 0);
+/// assert_eq!(even.check(&4), Ok(()));
+/// assert_eq!(even.check(&3), Err("3 is not even".to_owned()));
+/// ```
+pub fn predicate<F>(description: impl Into<String>, predicate: F) -> Predicate<F> {
+    Predicate {
+        description: description.into(),
+        predicate,
+    }
+}
+
+/// See [`predicate`].
+#[derive(Clone)]
+pub struct Predicate<F> {
+    description: String,
+    predicate: F,
+}
+
+impl<F> Debug for Predicate<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+impl<T: Debug + ?Sized, F: Fn(&T) -> bool> Matcher<T> for Predicate<F> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        if (self.predicate)(actual) {
+            Ok(())
+        } else {
+            Err(format!("{:?} is not {}", actual, self.description))
+        }
+    }
+}
+
+/// Matches `Some` values whose content is matched by `matcher`.
+pub fn some<M>(matcher: M) -> IsSome<M> {
+    IsSome(matcher)
+}
+
+/// See [`some`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IsSome<M>(M);
+
+impl<M: Debug> Debug for IsSome<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Some({:?})", self.0)
+    }
+}
+
+impl<T: Debug, M: Matcher<T>> Matcher<Option<T>> for IsSome<M> {
+    fn check(&self, actual: &Option<T>) -> Result<(), String> {
+        match actual {
+            Some(value) => self.0.check(value),
+            None => Err(format!("expected {:?}, got None", self)),
+        }
+    }
+}
+
+/// Matches `None`.
+pub fn none() -> IsNone {
+    IsNone
+}
+
+/// See [`none`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct IsNone;
+
+impl Debug for IsNone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("None")
+    }
+}
+
+impl<T: Debug> Matcher<Option<T>> for IsNone {
+    fn check(&self, actual: &Option<T>) -> Result<(), String> {
+        match actual {
+            None => Ok(()),
+            Some(_) => Err(format!("expected None, got {:?}", actual)),
+        }
+    }
+}
+
+/// Matches values not matched by `matcher`.
+pub fn not<M>(matcher: M) -> Not<M> {
+    Not(matcher)
+}
+
+/// See [`not`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Not<M>(M);
+
+impl<M: Debug> Debug for Not<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not {:?}", self.0)
+    }
+}
+
+impl<T: Debug + ?Sized, M: Matcher<T>> Matcher<T> for Not<M> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        match self.0.check(actual) {
+            Ok(()) => Err(format!("{:?} is {:?}", actual, self.0)),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+/// The descriptions of a list of matchers, see [`MatcherList`].
+pub trait DescribeList {
+    /// Writes the descriptions of the matchers, separated by commas.
+    fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// A list of matchers for [`all_of`] and [`any_of`], implemented for tuples of matchers, arrays and `Vec`s.
+pub trait MatcherList<T: ?Sized>: DescribeList {
+    /// Checks `actual` with every matcher of the list.
+    fn check_each(&self, actual: &T) -> Vec<Result<(), String>>;
+}
+
+impl<M: Debug> DescribeList for [M] {
+    fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, matcher) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{:?}", matcher)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: ?Sized, M: Matcher<T>> MatcherList<T> for [M] {
+    fn check_each(&self, actual: &T) -> Vec<Result<(), String>> {
+        self.iter().map(|matcher| matcher.check(actual)).collect()
+    }
+}
+
+impl<M: Debug, const LEN: usize> DescribeList for [M; LEN] {
+    fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self[..].describe(f)
+    }
+}
+
+impl<T: ?Sized, M: Matcher<T>, const LEN: usize> MatcherList<T> for [M; LEN] {
+    fn check_each(&self, actual: &T) -> Vec<Result<(), String>> {
+        self[..].check_each(actual)
+    }
+}
+
+impl<M: Debug> DescribeList for Vec<M> {
+    fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self[..].describe(f)
+    }
+}
+
+impl<T: ?Sized, M: Matcher<T>> MatcherList<T> for Vec<M> {
+    fn check_each(&self, actual: &T) -> Vec<Result<(), String>> {
+        self[..].check_each(actual)
+    }
+}
+
+macro_rules! impl_matcher_list {
+    ($first:ident $first_index:tt $(, $name:ident $index:tt)*) => {
+        impl<$first: Debug $(, $name: Debug)*> DescribeList for ($first, $($name,)*) {
+            fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{:?}", self.$first_index)?;
+                $(write!(f, ", {:?}", self.$index)?;)*
+                Ok(())
+            }
+        }
+
+        impl<T: ?Sized, $first: Matcher<T> $(, $name: Matcher<T>)*> MatcherList<T> for ($first, $($name,)*) {
+            fn check_each(&self, actual: &T) -> Vec<Result<(), String>> {
+                vec![self.$first_index.check(actual) $(, self.$index.check(actual))*]
+            }
+        }
+    };
+}
+
+impl_matcher_list!(A 0);
+impl_matcher_list!(A 0, B 1);
+impl_matcher_list!(A 0, B 1, C 2);
+impl_matcher_list!(A 0, B 1, C 2, D 3);
+impl_matcher_list!(A 0, B 1, C 2, D 3, E 4);
+impl_matcher_list!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_matcher_list!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_matcher_list!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+/// Matches values matched by all `matchers`, a tuple, array or `Vec` of matchers.
+///
+/// The reasons of all failing matchers are reported.
+pub fn all_of<L>(matchers: L) -> AllOf<L> {
+    AllOf(matchers)
+}
+
+/// See [`all_of`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AllOf<L>(L);
+
+impl<L: DescribeList> Debug for AllOf<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("all of [")?;
+        self.0.describe(f)?;
+        f.write_str("]")
+    }
+}
+
+impl<T: ?Sized, L: MatcherList<T>> Matcher<T> for AllOf<L> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        let reasons: Vec<String> = self
+            .0
+            .check_each(actual)
+            .into_iter()
+            .filter_map(Result::err)
+            .collect();
+        if reasons.is_empty() {
+            Ok(())
+        } else {
+            Err(reasons.join("; "))
+        }
+    }
+}
+
+/// Matches values matched by any of the `matchers`, a tuple, array or `Vec` of matchers.
+pub fn any_of<L>(matchers: L) -> AnyOf<L> {
+    AnyOf(matchers)
+}
+
+/// See [`any_of`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnyOf<L>(L);
+
+impl<L: DescribeList> Debug for AnyOf<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any of [")?;
+        self.0.describe(f)?;
+        f.write_str("]")
+    }
+}
+
+impl<T: ?Sized, L: MatcherList<T>> Matcher<T> for AnyOf<L> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        let checks = self.0.check_each(actual);
+        if checks.iter().any(Result::is_ok) {
+            Ok(())
+        } else {
+            let reasons: Vec<String> = checks.into_iter().filter_map(Result::err).collect();
+            Err(reasons.join("; "))
+        }
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    //! Autoref specialization accepting the expected panics of [`Matcher`]s in a [`collect_fails!`](crate::collect_fails) table.
+    use std::marker::PhantomData;
+
+    use super::Matcher;
+    use crate::{AssertOutput, Assertion, Verdict};
+
+    pub struct PanicProbe<'a, R, E>(pub &'a E, pub PhantomData<fn(&R)>);
+
+    pub trait ViaMatcher {
+        fn check_panic(&self, message: &str) -> Verdict;
+    }
+
+    impl<R, E: Matcher<R>> ViaMatcher for PanicProbe<'_, R, E> {
+        fn check_panic(&self, message: &str) -> Verdict {
+            self.0.check_panic(message).map_err(Some)
+        }
+    }
+
+    pub trait ViaPanicked {
+        fn check_panic(&self, message: &str) -> Verdict;
+    }
+
+    impl<R, E> ViaPanicked for &PanicProbe<'_, R, E> {
+        fn check_panic(&self, _: &str) -> Verdict {
+            Err(None)
+        }
+    }
+
+    /// The assertion of a table, with the check of panics selected by [`PanicProbe`].
+    pub struct MacroAssertion<F, P>(F, P);
+
+    pub fn assertion<R, E, F, O, P>(assert: F, check_panic: P) -> MacroAssertion<F, P>
+    where
+        F: FnMut(&R, &E) -> O,
+        O: AssertOutput,
+        P: FnMut(&str, &E) -> Verdict,
+    {
+        MacroAssertion(assert, check_panic)
+    }
+
+    impl<R, E, F, O, P> Assertion<R, E> for MacroAssertion<F, P>
+    where
+        F: FnMut(&R, &E) -> O,
+        O: AssertOutput,
+        P: FnMut(&str, &E) -> Verdict,
+    {
+        fn assert(&mut self, result: &R, expected: &E) -> Verdict {
+            (self.0)(result, expected).into_verdict()
+        }
+
+        fn assert_panic(&mut self, message: &str, expected: &E) -> Verdict {
+            (self.1)(message, expected)
+        }
+    }
+}
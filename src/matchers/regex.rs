@@ -0,0 +1,441 @@
+//! A small regular expression engine for [`matches_regex`](super::matches_regex).
+//!
+//! Supports literals, `.`, character classes `[a-z]`, `[^0-9]`, the escapes `\d`, `\w`, `\s` and their
+//! negations, anchors `^` and `$`, groups `(...)` with alternation `|`, and the greedy and lazy
+//! quantifiers `*`, `+`, `?` and `{n}`, `{n,}`, `{n,m}`.
+//!
+//! The expression is compiled into the instructions of a nondeterministic automaton, which is run on
+//! all threads in lockstep (a Pike VM), in time linear in the length of the text.
+
+use std::fmt::{self, Display};
+use std::mem;
+
+/// The maximum count of a counted quantifier, bounding the size of the compiled program.
+const MAX_REPEAT: usize = 1000;
+
+/// A parsed regular expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Regex {
+    source: String,
+    program: Vec<Inst>,
+}
+
+/// The error returned when parsing an invalid regular expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RegexError {
+    pattern: String,
+    position: usize,
+    message: &'static str,
+}
+
+impl Display for RegexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid regex {:?} at {}: {}",
+            self.pattern, self.position, self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Char(char),
+    Any,
+    Class(bool, Vec<ClassItem>),
+    Start,
+    End,
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    /// A quantified node, whether greedy or lazy does not change if the expression matches.
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+
+impl ClassItem {
+    fn matches(&self, c: char) -> bool {
+        match *self {
+            ClassItem::Range(start, end) => start <= c && c <= end,
+            ClassItem::Digit(negated) => c.is_ascii_digit() != negated,
+            ClassItem::Word(negated) => (c.is_alphanumeric() || c == '_') != negated,
+            ClassItem::Space(negated) => c.is_whitespace() != negated,
+        }
+    }
+}
+
+/// An instruction of the compiled program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Inst {
+    /// Consumes the character.
+    Char(char),
+    /// Consumes any character except a line break.
+    Any,
+    /// Consumes a character of the class, or not of the class if negated.
+    Class(bool, Vec<ClassItem>),
+    /// Continues at the start of the text.
+    Start,
+    /// Continues at the end of the text.
+    End,
+    /// Continues at both instructions.
+    Split(usize, usize),
+    /// Continues at the instruction.
+    Jump(usize),
+    /// The expression matched.
+    Match,
+}
+
+impl Inst {
+    /// Whether the consuming instruction accepts `c`.
+    fn accepts(&self, c: char) -> bool {
+        match self {
+            Inst::Char(expected) => *expected == c,
+            Inst::Any => c != '\n',
+            Inst::Class(negated, items) => items.iter().any(|item| item.matches(c)) != *negated,
+            _ => false,
+        }
+    }
+}
+
+impl Regex {
+    pub(crate) fn new(pattern: &str) -> Result<Self, RegexError> {
+        let mut parser = Parser {
+            pattern,
+            chars: pattern.chars().collect(),
+            pos: 0,
+        };
+        let node = parser.alternation()?;
+        if parser.pos < parser.chars.len() {
+            return Err(parser.error("unmatched `)`"));
+        }
+        let mut program = Vec::new();
+        compile(&node, &mut program);
+        program.push(Inst::Match);
+        Ok(Self {
+            source: pattern.to_owned(),
+            program,
+        })
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns `true` if the expression matches anywhere in `text`.
+    pub(crate) fn is_match(&self, text: &str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        let mut current = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+        for (pos, c) in chars.iter().map(Some).chain([None]).enumerate() {
+            // a match may start at any position
+            if current.add(&self.program, 0, pos, chars.len()) {
+                return true;
+            }
+            let Some(&c) = c else {
+                break;
+            };
+            next.clear();
+            for &pc in &current.pcs {
+                if self.program[pc].accepts(c)
+                    && next.add(&self.program, pc + 1, pos + 1, chars.len())
+                {
+                    return true;
+                }
+            }
+            mem::swap(&mut current, &mut next);
+        }
+        false
+    }
+}
+
+/// Appends the instructions matching `node` to `program`.
+fn compile(node: &Node, program: &mut Vec<Inst>) {
+    match node {
+        Node::Char(c) => program.push(Inst::Char(*c)),
+        Node::Any => program.push(Inst::Any),
+        Node::Class(negated, items) => program.push(Inst::Class(*negated, items.clone())),
+        Node::Start => program.push(Inst::Start),
+        Node::End => program.push(Inst::End),
+        Node::Concat(nodes) => nodes.iter().for_each(|node| compile(node, program)),
+        Node::Alternation(nodes) => {
+            let mut jumps = Vec::new();
+            for (index, node) in nodes.iter().enumerate() {
+                if index + 1 == nodes.len() {
+                    compile(node, program);
+                    break;
+                }
+                let split = program.len();
+                program.push(Inst::Split(split + 1, 0));
+                compile(node, program);
+                jumps.push(program.len());
+                program.push(Inst::Jump(0));
+                program[split] = Inst::Split(split + 1, program.len());
+            }
+            let end = program.len();
+            for jump in jumps {
+                program[jump] = Inst::Jump(end);
+            }
+        }
+        Node::Repeat { node, min, max } => {
+            for _ in 0..*min {
+                compile(node, program);
+            }
+            match max {
+                None => {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    compile(node, program);
+                    program.push(Inst::Jump(split));
+                    program[split] = Inst::Split(split + 1, program.len());
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(program.len());
+                        program.push(Inst::Split(program.len() + 1, 0));
+                        compile(node, program);
+                    }
+                    let end = program.len();
+                    for split in splits {
+                        program[split] = Inst::Split(split + 1, end);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The threads of the program at a position of the text, each waiting at a consuming instruction.
+struct Threads {
+    pcs: Vec<usize>,
+    /// The instructions visited at the position, so each is followed once.
+    seen: Vec<bool>,
+    stack: Vec<usize>,
+}
+
+impl Threads {
+    fn new(len: usize) -> Self {
+        Self {
+            pcs: Vec::new(),
+            seen: vec![false; len],
+            stack: Vec::new(),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.pcs.clear();
+        self.seen.fill(false);
+    }
+
+    /// Adds the threads reachable from `pc` without consuming a character at `pos` of a text of `len`
+    /// characters, returning `true` if one of them reaches the end of the program.
+    fn add(&mut self, program: &[Inst], pc: usize, pos: usize, len: usize) -> bool {
+        self.stack.push(pc);
+        while let Some(pc) = self.stack.pop() {
+            if mem::replace(&mut self.seen[pc], true) {
+                continue;
+            }
+            match program[pc] {
+                Inst::Start if pos == 0 => self.stack.push(pc + 1),
+                Inst::End if pos == len => self.stack.push(pc + 1),
+                Inst::Start | Inst::End => {}
+                Inst::Split(first, second) => self.stack.extend([second, first]),
+                Inst::Jump(to) => self.stack.push(to),
+                Inst::Match => {
+                    self.stack.clear();
+                    return true;
+                }
+                Inst::Char(_) | Inst::Any | Inst::Class(..) => self.pcs.push(pc),
+            }
+        }
+        false
+    }
+}
+
+struct Parser<'a> {
+    pattern: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &'static str) -> RegexError {
+        RegexError {
+            pattern: self.pattern.to_owned(),
+            position: self.pos,
+            message,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn alternation(&mut self) -> Result<Node, RegexError> {
+        let mut alternatives = vec![self.concat()?];
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            alternatives.push(self.concat()?);
+        }
+        Ok(if alternatives.len() == 1 {
+            alternatives.pop().expect("one alternative")
+        } else {
+            Node::Alternation(alternatives)
+        })
+    }
+
+    fn concat(&mut self) -> Result<Node, RegexError> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.quantifier(atom)?);
+        }
+        Ok(Node::Concat(nodes))
+    }
+
+    fn atom(&mut self) -> Result<Node, RegexError> {
+        let c = self.peek().ok_or_else(|| self.error("unexpected end"))?;
+        self.pos += 1;
+        Ok(match c {
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '(' => {
+                // non-capturing groups are equivalent, as groups do not capture
+                if self.chars[self.pos..].starts_with(&['?', ':']) {
+                    self.pos += 2;
+                }
+                let node = self.alternation()?;
+                if self.peek() != Some(')') {
+                    return Err(self.error("unclosed group"));
+                }
+                self.pos += 1;
+                node
+            }
+            '[' => self.class()?,
+            '\\' => match self.escape()? {
+                Ok(c) => Node::Char(c),
+                Err(item) => Node::Class(false, vec![item]),
+            },
+            '*' | '+' | '?' | '{' => return Err(self.error("quantifier without expression")),
+            c => Node::Char(c),
+        })
+    }
+
+    /// Parses the escaped character or class after a backslash.
+    fn escape(&mut self) -> Result<Result<char, ClassItem>, RegexError> {
+        let c = self
+            .peek()
+            .ok_or_else(|| self.error("unexpected end after `\\`"))?;
+        self.pos += 1;
+        Ok(match c {
+            'd' => Err(ClassItem::Digit(false)),
+            'D' => Err(ClassItem::Digit(true)),
+            'w' => Err(ClassItem::Word(false)),
+            'W' => Err(ClassItem::Word(true)),
+            's' => Err(ClassItem::Space(false)),
+            'S' => Err(ClassItem::Space(true)),
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            'r' => Ok('\r'),
+            c => Ok(c),
+        })
+    }
+
+    fn class(&mut self) -> Result<Node, RegexError> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut items = Vec::new();
+        loop {
+            let c = self
+                .peek()
+                .ok_or_else(|| self.error("unclosed character class"))?;
+            self.pos += 1;
+            let start = match c {
+                ']' if !items.is_empty() => return Ok(Node::Class(negated, items)),
+                '\\' => match self.escape()? {
+                    Ok(c) => c,
+                    Err(item) => {
+                        items.push(item);
+                        continue;
+                    }
+                },
+                c => c,
+            };
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|c| *c != ']') {
+                let end = self.chars[self.pos + 1];
+                self.pos += 2;
+                if end < start {
+                    return Err(self.error("invalid character range"));
+                }
+                items.push(ClassItem::Range(start, end));
+            } else {
+                items.push(ClassItem::Range(start, start));
+            }
+        }
+    }
+
+    fn quantifier(&mut self, node: Node) -> Result<Node, RegexError> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => return self.counted(node),
+            _ => return Ok(node),
+        };
+        self.pos += 1;
+        Ok(self.repeat(node, min, max))
+    }
+
+    /// Parses the counted quantifiers `{n}`, `{n,}` and `{n,m}`.
+    fn counted(&mut self, node: Node) -> Result<Node, RegexError> {
+        let close = self.chars[self.pos..]
+            .iter()
+            .position(|c| *c == '}')
+            .ok_or_else(|| self.error("unclosed quantifier"))?;
+        let content: String = self.chars[self.pos + 1..self.pos + close].iter().collect();
+        let parse = |count: &str| count.trim().parse::<usize>().ok();
+        let invalid = || self.error("invalid quantifier");
+        let (min, max) = match content.split_once(',') {
+            None => (parse(&content), parse(&content)),
+            Some((min, max)) if max.trim().is_empty() => (parse(min), None),
+            Some((min, max)) => (parse(min), Some(parse(max).ok_or_else(invalid)?)),
+        };
+        let min = min.ok_or_else(invalid)?;
+        if max.is_some_and(|max| max < min) {
+            return Err(invalid());
+        }
+        if max.unwrap_or(min) > MAX_REPEAT {
+            return Err(self.error("quantifier too large"));
+        }
+        self.pos += close + 1;
+        Ok(self.repeat(node, min, max))
+    }
+
+    fn repeat(&mut self, node: Node, min: usize, max: Option<usize>) -> Node {
+        // the lazy suffix `?` does not change if the expression matches
+        if self.peek() == Some('?') {
+            self.pos += 1;
+        }
+        Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+        }
+    }
+}
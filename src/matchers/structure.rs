@@ -0,0 +1,199 @@
+//! Matchers of parts of a value, found in its `Debug` representation.
+
+use std::fmt::{self, Debug};
+
+use super::Matcher;
+use crate::tiny_diff::join_differences;
+use crate::{tree, Difference};
+
+/// Matches values whose fields at the listed paths equal the expected values, ignoring all other fields.
+///
+/// Paths are written like the differing paths of the report, such as `kind`, `.span.end`, `.0`, `[3]`
+/// or `["key"]`, and are resolved in the `Debug` representation of the value. The expected values are
+/// compared by their `Debug` representation.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{fields, matches, Fields};
+/// use tiny_test::collect_fails;
+///
+/// #[derive(Debug)]
+/// enum Kind {
+///     Ident,
+///     Number,
+/// }
+///
+/// #[derive(Debug)]
+/// struct Span {
+///     start: usize,
+///     end: usize,
+/// }
+///
+/// #[derive(Debug)]
+/// struct Token {
+///     kind: Kind,
+///     span: Span,
+///     hash: u64,
+/// }
+///
+/// fn lex(input: &str) -> Token {
+///     let kind = if input.starts_with(|c: char| c.is_ascii_digit()) { Kind::Number } else { Kind::Ident };
+///     Token { kind, span: Span { start: 0, end: input.len() }, hash: input.len() as u64 * 31 }
+/// }
+///
+/// let fails = collect_fails!(
+///     &str,
+///     Fields,
+///     Token,
+///     [
+///         "abc" => fields().field("kind", Kind::Ident).field("span.end", 2),
+///         "12" => fields().field("kind", Kind::Number).field(".span.end", 2),
+///     ],
+///     |input| lex(input),
+///     matches
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(fails.fails()[0].reason(), Some(".span.end: expected 2, got 3"));
+/// assert!(fails.to_string().contains("\texpected `with fields .kind: Ident, .span.end: 2`\n"));
+/// ```
+pub fn fields() -> Fields {
+    Fields::default()
+}
+
+/// See [`fields`].
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Fields {
+    fields: Vec<(String, String)>,
+}
+
+impl Fields {
+    /// Expects the value at `path` to equal `expected`.
+    pub fn field(mut self, path: impl Into<String>, expected: impl Debug) -> Self {
+        let path = path.into();
+        let path = if path.starts_with(['.', '[']) {
+            path
+        } else {
+            format!(".{}", path)
+        };
+        self.fields.push((path, format!("{:?}", expected)));
+        self
+    }
+}
+
+impl Debug for Fields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("with fields")?;
+        for (index, (path, expected)) in self.fields.iter().enumerate() {
+            let separator = if index == 0 { " " } else { ", " };
+            write!(f, "{}{}: {}", separator, path, expected)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug + ?Sized> Matcher<T> for Fields {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        let text = format!("{:?}", actual);
+        let value = parse_debug(&text)?;
+        let mut differences = Vec::new();
+        for (path, expected) in &self.fields {
+            let field = match value.get(path) {
+                Ok(field) => field,
+                Err(missing) => {
+                    differences.push(Difference::new(
+                        path.as_str(),
+                        format!("expected {}, {} is missing", expected, missing),
+                    ));
+                    continue;
+                }
+            };
+            match tree::parse(expected) {
+                Some(expected) => {
+                    differences.extend(tree::match_pattern(&expected, field).into_iter().map(
+                        |difference| {
+                            Difference::new(
+                                format!("{}{}", path, difference.path()),
+                                difference.message(),
+                            )
+                        },
+                    ))
+                }
+                None if expected != field.text() => differences.push(Difference::new(
+                    path.as_str(),
+                    format!("expected {}, got {}", expected, field.text()),
+                )),
+                None => {}
+            }
+        }
+        if differences.is_empty() {
+            Ok(())
+        } else {
+            Err(join_differences(&differences))
+        }
+    }
+}
+
+/// Matches values whose `Debug` representation matches `pattern`, a `Debug` representation with wildcards.
+///
+/// In the pattern `_` matches any value, a struct ending with `..` ignores the omitted fields,
+/// and a tuple or list ending with `..` ignores the remaining items.
+///
+/// # Panics
+/// Panics if the pattern is not a `Debug` representation of structs, tuples, lists, maps and values.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{debug_pattern, Matcher};
+///
+/// #[derive(Debug)]
+/// struct Token {
+///     kind: &'static str,
+///     span: (usize, usize),
+///     hash: u64,
+/// }
+///
+/// let matcher = debug_pattern(r#"Token { kind: "ident", span: (0, _), .. }"#);
+/// assert_eq!(matcher.check(&Token { kind: "ident", span: (0, 3), hash: 7 }), Ok(()));
+/// assert_eq!(
+///     matcher.check(&Token { kind: "number", span: (1, 3), hash: 7 }),
+///     Err(r#".kind: expected "ident", got "number"; .span.0: expected 0, got 1"#.to_owned())
+/// );
+/// ```
+#[track_caller]
+pub fn debug_pattern(pattern: impl Into<String>) -> DebugPattern {
+    let pattern = pattern.into();
+    if tree::parse(&pattern).is_none() {
+        panic!("invalid debug pattern {:?}", pattern);
+    }
+    DebugPattern(pattern)
+}
+
+/// See [`debug_pattern`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DebugPattern(String);
+
+impl Debug for DebugPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "like {}", self.0)
+    }
+}
+
+impl<T: Debug + ?Sized> Matcher<T> for DebugPattern {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        let text = format!("{:?}", actual);
+        let value = parse_debug(&text)?;
+        let pattern = tree::parse(&self.0).expect("the pattern is parsed on creation");
+        let differences = tree::match_pattern(&pattern, &value);
+        if differences.is_empty() {
+            Ok(())
+        } else {
+            Err(join_differences(&differences))
+        }
+    }
+}
+
+/// Parses the `Debug` representation of a value, `Err` with the reason if it is not understood.
+fn parse_debug(text: &str) -> Result<tree::Node<'_>, String> {
+    tree::parse(text).ok_or_else(|| format!("unable to parse the Debug representation {}", text))
+}
@@ -0,0 +1,185 @@
+//! Matchers of the `Display` and `Debug` text of values.
+
+use std::fmt::{self, Debug, Display};
+
+use super::Matcher;
+use crate::diff;
+
+/// Matches values whose `Display` text equals `expected`.
+///
+/// A mismatch of multi-line texts is explained by their line-by-line diff.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{displays, Matcher};
+///
+/// assert_eq!(displays("1.5").check(&1.5), Ok(()));
+/// assert_eq!(displays("a  b\n").normalize_whitespace().check(" a b "), Ok(()));
+/// assert_eq!(
+///     displays("fn main() {\n    run();\n}").check("fn main() {\n    exit();\n}"),
+///     Err("diff of expected (-) and result (+):\n  \
+///          fn main() {\n\
+///          -     run();\n\
+///          +     exit();\n  \
+///          }\n"
+///         .to_owned())
+/// );
+/// ```
+pub fn displays(expected: impl Into<String>) -> Text<Displayed> {
+    Text::new(expected.into())
+}
+
+/// Matches values whose `Debug` text equals `expected`.
+///
+/// The compact `Debug` text is compared, unless `expected` spans multiple lines, then the pretty
+/// printed text is compared.
+pub fn debugs(expected: impl Into<String>) -> Text<Debugged> {
+    Text::new(expected.into())
+}
+
+/// The assertion comparing the `Display` text of the result with the expected string, see [`displays`].
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::display_eq;
+/// use tiny_test::collect_fails;
+///
+/// let fails = collect_fails!(
+///     Vec<u32>,
+///     &str,
+///     String,
+///     vec![(vec![1, 2], "1/2"), (vec![3, 4], "3 / 4"), (vec![5, 6, 7], "5\n6\n8")].into_iter(),
+///     |parts: &Vec<u32>| {
+///         let parts: Vec<String> = parts.iter().map(ToString::to_string).collect();
+///         parts.join(if parts.len() > 2 { "\n" } else { "/" })
+///     },
+///     display_eq
+/// );
+///
+/// assert_eq!(fails.len(), 2);
+/// assert_eq!(fails.fails()[0].reason(), Some("expected \"3 / 4\", got \"3/4\""));
+/// assert!(fails.to_string().contains(
+///     "\treason: diff of expected (-) and result (+):\n\t  5\n\t  6\n\t- 8\n\t+ 7\n"
+/// ));
+/// ```
+pub fn display_eq<R, E>(result: &R, expected: &E) -> Result<(), String>
+where
+    R: Display + ?Sized,
+    E: AsRef<str> + ?Sized,
+{
+    displays(expected.as_ref()).check(result)
+}
+
+/// The assertion comparing the `Debug` text of the result with the expected string, see [`debugs`].
+pub fn debug_eq<R, E>(result: &R, expected: &E) -> Result<(), String>
+where
+    R: Debug + ?Sized,
+    E: AsRef<str> + ?Sized,
+{
+    debugs(expected.as_ref()).check(result)
+}
+
+/// The `Display` text of a value, see [`displays`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Displayed;
+
+/// The `Debug` text of a value, see [`debugs`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Debugged;
+
+/// See [`displays`] and [`debugs`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Text<F> {
+    expected: String,
+    normalize_whitespace: bool,
+    format: F,
+}
+
+impl<F: Default> Text<F> {
+    fn new(expected: String) -> Self {
+        Self {
+            expected,
+            normalize_whitespace: false,
+            format: F::default(),
+        }
+    }
+}
+
+impl<F> Text<F> {
+    /// Ignores leading and trailing whitespace of lines and blank lines at the start and end,
+    /// and treats runs of whitespace as a single space.
+    pub fn normalize_whitespace(mut self) -> Self {
+        self.normalize_whitespace = true;
+        self
+    }
+
+    /// Compares the `actual` text with the expected text.
+    fn compare(&self, actual: &str) -> Result<(), String> {
+        let (expected, actual) = if self.normalize_whitespace {
+            (normalize(&self.expected), normalize(actual))
+        } else {
+            (self.expected.clone(), actual.to_owned())
+        };
+        if expected == actual {
+            Ok(())
+        } else if !expected.contains('\n') && !actual.contains('\n') {
+            Err(format!("expected {:?}, got {:?}", expected, actual))
+        } else {
+            let mut reason = "diff of expected (-) and result (+):\n".to_owned();
+            diff::write_unified(&mut reason, "", &expected, &actual)
+                .expect("writing to a String never fails");
+            Err(reason)
+        }
+    }
+
+    /// Writes the description of the matcher, the expected text formatted by `format`.
+    fn describe(&self, f: &mut fmt::Formatter<'_>, format: &str) -> fmt::Result {
+        write!(f, "{} as {:?}", format, self.expected)?;
+        if self.normalize_whitespace {
+            f.write_str(" ignoring whitespace")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Text<Displayed> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.describe(f, "displayed")
+    }
+}
+
+impl Debug for Text<Debugged> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.describe(f, "debugged")
+    }
+}
+
+impl<T: Display + ?Sized> Matcher<T> for Text<Displayed> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        self.compare(&actual.to_string())
+    }
+}
+
+impl<T: Debug + ?Sized> Matcher<T> for Text<Debugged> {
+    fn check(&self, actual: &T) -> Result<(), String> {
+        if self.expected.contains('\n') {
+            self.compare(&format!("{:#?}", actual))
+        } else {
+            self.compare(&format!("{:?}", actual))
+        }
+    }
+}
+
+/// Trims the lines of `text`, removes blank lines at its start and end, and replaces runs of whitespace by a space.
+fn normalize(text: &str) -> String {
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    let start = lines.iter().position(|line| !line.is_empty());
+    let end = lines.iter().rposition(|line| !line.is_empty());
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    }
+}
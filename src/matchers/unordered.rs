@@ -0,0 +1,208 @@
+//! Matchers of collections ignoring the order of their items.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::{self, Debug};
+
+use super::Matcher;
+
+/// A collection whose items are compared ignoring their order, see [`unordered`].
+pub trait Items {
+    /// An item of the collection, or an entry of a map.
+    type Item<'a>: PartialEq + Debug
+    where
+        Self: 'a;
+
+    /// The items of the collection.
+    fn items(&self) -> Vec<Self::Item<'_>>;
+}
+
+impl<T: Items + ?Sized> Items for &T {
+    type Item<'a>
+        = T::Item<'a>
+    where
+        Self: 'a;
+
+    fn items(&self) -> Vec<Self::Item<'_>> {
+        (**self).items()
+    }
+}
+
+macro_rules! impl_items {
+    ($($ty:ty $(, const $len:ident)?);* $(;)?) => {
+        $(
+            impl<T: PartialEq + Debug $(, const $len: usize)?> Items for $ty {
+                type Item<'a>
+                    = &'a T
+                where
+                    Self: 'a;
+
+                fn items(&self) -> Vec<Self::Item<'_>> {
+                    self.iter().collect()
+                }
+            }
+        )*
+    };
+}
+
+impl_items!([T]; [T; N], const N; Vec<T>; VecDeque<T>; BTreeSet<T>);
+
+impl<T: PartialEq + Debug, S> Items for HashSet<T, S> {
+    type Item<'a>
+        = &'a T
+    where
+        Self: 'a;
+
+    fn items(&self) -> Vec<Self::Item<'_>> {
+        self.iter().collect()
+    }
+}
+
+impl<K: PartialEq + Debug, V: PartialEq + Debug> Items for BTreeMap<K, V> {
+    type Item<'a>
+        = (&'a K, &'a V)
+    where
+        Self: 'a;
+
+    fn items(&self) -> Vec<Self::Item<'_>> {
+        self.iter().collect()
+    }
+}
+
+impl<K: PartialEq + Debug, V: PartialEq + Debug, S> Items for HashMap<K, V, S> {
+    type Item<'a>
+        = (&'a K, &'a V)
+    where
+        Self: 'a;
+
+    fn items(&self) -> Vec<Self::Item<'_>> {
+        self.iter().collect()
+    }
+}
+
+/// Matches collections with the items of `expected` in any order, each item as often as in `expected`.
+///
+/// A mismatch lists the missing and unexpected items. Maps are compared by their entries, so an entry
+/// with a different value is both missing and unexpected.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::matchers::{matches, unordered, Unordered};
+/// use tiny_test::collect_fails;
+///
+/// fn divisors(n: &u32) -> Vec<u32> {
+///     let mut divisors: Vec<u32> = (1..=*n).filter(|d| n % d == 0).collect();
+///     divisors.reverse();
+///     divisors
+/// }
+///
+/// let fails = collect_fails!(
+///     u32,
+///     Unordered<Vec<u32>>,
+///     Vec<u32>,
+///     [
+///         6 => unordered(vec![1, 2, 3, 6]),
+///         8 => unordered(vec![1, 2, 2, 8, 9]),
+///     ],
+///     divisors,
+///     matches
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(fails.fails()[0].reason(), Some("missing: [2, 9], unexpected: [4]"));
+/// ```
+pub fn unordered<C: Items>(expected: C) -> Unordered<C> {
+    Unordered {
+        expected,
+        set: false,
+    }
+}
+
+/// Matches collections with the same distinct items as `expected`, ignoring their order and repetitions.
+///
+/// # Examples
+/// ```rust
+/// use std::collections::HashMap;
+/// use tiny_test::matchers::{unordered_set, Matcher};
+///
+/// let matcher = unordered_set(vec!["a", "b"]);
+/// assert_eq!(matcher.check(&vec!["b", "a", "b"]), Ok(()));
+/// assert_eq!(
+///     matcher.check(&vec!["a", "c", "c"]),
+///     Err("missing: [\"b\"], unexpected: [\"c\"]".to_owned())
+/// );
+///
+/// let scores = HashMap::from([("ann", 3), ("bob", 5)]);
+/// assert_eq!(
+///     unordered_set(HashMap::from([("ann", 3), ("bob", 4)])).check(&scores),
+///     Err("missing: [(\"bob\", 4)], unexpected: [(\"bob\", 5)]".to_owned())
+/// );
+/// ```
+pub fn unordered_set<C: Items>(expected: C) -> Unordered<C> {
+    Unordered {
+        expected,
+        set: true,
+    }
+}
+
+/// See [`unordered`] and [`unordered_set`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct Unordered<C> {
+    expected: C,
+    set: bool,
+}
+
+impl<C: Debug> Debug for Unordered<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.set {
+            write!(f, "the set of {:?}", self.expected)
+        } else {
+            write!(f, "in any order {:?}", self.expected)
+        }
+    }
+}
+
+impl<C: Items + Debug> Matcher<C> for Unordered<C> {
+    fn check(&self, actual: &C) -> Result<(), String> {
+        let expected = self.expected.items();
+        let actual = actual.items();
+        let (missing, unexpected) = if self.set {
+            (
+                distinct(expected.iter().filter(|item| !actual.contains(item))),
+                distinct(actual.iter().filter(|item| !expected.contains(item))),
+            )
+        } else {
+            let mut unmatched: Vec<Option<&C::Item<'_>>> = actual.iter().map(Some).collect();
+            let mut missing = Vec::new();
+            for item in &expected {
+                match unmatched.iter_mut().find(|other| *other == &Some(item)) {
+                    Some(other) => *other = None,
+                    None => missing.push(item),
+                }
+            }
+            (missing, unmatched.into_iter().flatten().collect())
+        };
+        let mut reasons = Vec::new();
+        if !missing.is_empty() {
+            reasons.push(format!("missing: {:?}", missing));
+        }
+        if !unexpected.is_empty() {
+            reasons.push(format!("unexpected: {:?}", unexpected));
+        }
+        if reasons.is_empty() {
+            Ok(())
+        } else {
+            Err(reasons.join(", "))
+        }
+    }
+}
+
+/// Collects the distinct `items`, in order of their first occurrence.
+fn distinct<'i, T: PartialEq>(items: impl Iterator<Item = &'i T>) -> Vec<&'i T> {
+    let mut distinct: Vec<&T> = Vec::new();
+    for item in items {
+        if !distinct.contains(&item) {
+            distinct.push(item);
+        }
+    }
+    distinct
+}
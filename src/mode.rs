@@ -0,0 +1,97 @@
+use std::env;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Determines how failed test-cases are handled while running a table.
+///
+/// The mode is selected per table, otherwise by the [`FailMode::ENV_VAR`] environment variable,
+/// falling back to [`FailMode::Collect`].
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{collect_fails, FailMode};
+///
+/// let fails = collect_fails!(
+///     mode = FailMode::BreakOnFailure;
+///     usize,
+///     usize,
+///     vec![(1, 2), (2, 5), (3, 7)].into_iter(),
+///     |input| input * 2
+/// );
+///
+/// // the third case is never run
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!("panic-first".parse(), Ok(FailMode::PanicFirst));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FailMode {
+    /// Runs all test-cases, collecting every failure.
+    #[default]
+    Collect,
+    /// Panics with the report of the first failed test-case.
+    PanicFirst,
+    /// Stops running test-cases after the first failure, returning the report.
+    BreakOnFailure,
+}
+
+impl FailMode {
+    /// The environment variable selecting the mode of tables that do not specify one.
+    ///
+    /// Accepts `collect`, `panic-first` and `break-on-failure` case-insensitively.
+    pub const ENV_VAR: &'static str = "TINY_TEST_MODE";
+
+    /// Reads the mode from the [`FailMode::ENV_VAR`] environment variable.
+    /// - Returns `None` if the variable is not set.
+    /// - Panics if the variable does not hold a valid mode.
+    pub fn from_env() -> Option<Self> {
+        let value = env::var_os(Self::ENV_VAR)?;
+        match value.to_str().map(str::parse) {
+            Some(Ok(mode)) => Some(mode),
+            _ => panic!(
+                "invalid value {:?} of {}, expected one of `collect`, `panic-first` or `break-on-failure`",
+                value,
+                Self::ENV_VAR
+            ),
+        }
+    }
+
+    /// Selects the `explicit` mode of a table, otherwise the mode of the environment or the default.
+    pub fn resolve(explicit: Option<Self>) -> Self {
+        explicit.or_else(Self::from_env).unwrap_or_default()
+    }
+}
+
+impl Display for FailMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FailMode::Collect => "collect",
+            FailMode::PanicFirst => "panic-first",
+            FailMode::BreakOnFailure => "break-on-failure",
+        })
+    }
+}
+
+/// The error returned when parsing an unknown [`FailMode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFailModeError(String);
+
+impl Display for ParseFailModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown fail mode `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseFailModeError {}
+
+impl FromStr for FailMode {
+    type Err = ParseFailModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "collect" => Ok(FailMode::Collect),
+            "panic-first" => Ok(FailMode::PanicFirst),
+            "break-on-failure" | "break" => Ok(FailMode::BreakOnFailure),
+            _ => Err(ParseFailModeError(s.to_owned())),
+        }
+    }
+}
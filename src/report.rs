@@ -0,0 +1,580 @@
+use std::any::Any;
+use std::fmt::{self, Debug, Display, Write};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{diff, tree, Case, Difference, Location};
+
+/// The outcome of invoking the test function for a single test-case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<R> {
+    /// The test function returned a value.
+    Returned(R),
+    /// The test function panicked with the contained message.
+    Panicked(String),
+}
+
+impl<R> Outcome<R> {
+    /// Invokes `test`, catching a panic instead of unwinding into the caller.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tiny_test::Outcome;
+    ///
+    /// assert_eq!(Outcome::catch(|| 1 + 1), Outcome::Returned(2));
+    /// assert_eq!(
+    ///     Outcome::<()>::catch(|| panic!("oh no")).panic_message(),
+    ///     Some("oh no")
+    /// );
+    /// ```
+    pub fn catch(test: impl FnOnce() -> R) -> Self {
+        match panic::catch_unwind(AssertUnwindSafe(test)) {
+            Ok(result) => Outcome::Returned(result),
+            Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+        }
+    }
+
+    /// The returned value, if the test function did not panic.
+    pub fn returned(&self) -> Option<&R> {
+        match self {
+            Outcome::Returned(result) => Some(result),
+            Outcome::Panicked(_) => None,
+        }
+    }
+
+    /// The panic message, if the test function panicked.
+    pub fn panic_message(&self) -> Option<&str> {
+        match self {
+            Outcome::Returned(_) => None,
+            Outcome::Panicked(message) => Some(message),
+        }
+    }
+
+    /// Returns `true` if the test function panicked.
+    pub fn is_panicked(&self) -> bool {
+        matches!(self, Outcome::Panicked(_))
+    }
+}
+
+/// Extracts the message of a panic payload, which is either a `String` or a `&str`.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_owned()
+    }
+}
+
+/// A single failed test-case, as collected by [`collect_fails!`](crate::collect_fails).
+///
+/// Holds the 1-based number, the optional name and source location of the case in its table,
+/// the input fed to the test function, the expected value and the outcome that failed the assertion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseFailure<I, E, R> {
+    case_id: usize,
+    name: Option<String>,
+    location: Option<Location>,
+    input: I,
+    expected: E,
+    outcome: Outcome<R>,
+    reason: Option<String>,
+    differences: Vec<Difference>,
+}
+
+impl<I, E, R> CaseFailure<I, E, R> {
+    /// Creates a failure record for the test case with the 1-based number `case_id`.
+    pub fn new(case_id: usize, input: I, expected: E, result: R) -> Self {
+        Self::with_outcome(case_id, input, expected, Outcome::Returned(result))
+    }
+
+    /// Creates a failure record for a test case whose test function panicked with `message`.
+    pub fn panicked(case_id: usize, input: I, expected: E, message: impl Into<String>) -> Self {
+        Self::with_outcome(case_id, input, expected, Outcome::Panicked(message.into()))
+    }
+
+    /// Creates a failure record from the `outcome` of the test function.
+    pub fn with_outcome(case_id: usize, input: I, expected: E, outcome: Outcome<R>) -> Self {
+        Self {
+            case_id,
+            name: None,
+            location: None,
+            input,
+            expected,
+            outcome,
+            reason: None,
+            differences: Vec::new(),
+        }
+    }
+
+    /// Creates a failure record of the `case` with the 1-based number `case_id` from the `outcome` of the test function.
+    pub fn from_case(case_id: usize, case: Case<I, E>, outcome: Outcome<R>) -> Self {
+        let (name, location, input, expected) = case.into_parts();
+        Self {
+            case_id,
+            name,
+            location,
+            input,
+            expected,
+            outcome,
+            reason: None,
+            differences: Vec::new(),
+        }
+    }
+
+    /// Sets the name identifying the test case in the report.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the location of the test case in its source file.
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Sets the reason the assertion failed, see [`Assertion`](crate::Assertion).
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Sets the differences of the expected value and the result, see [`TinyDiff`](crate::TinyDiff).
+    ///
+    /// When empty, the report lists the differences of their parsed `Debug` representations instead.
+    pub fn with_differences(mut self, differences: Vec<Difference>) -> Self {
+        self.differences = differences;
+        self
+    }
+
+    /// The 1-based number of the test case in its table.
+    pub fn case_id(&self) -> usize {
+        self.case_id
+    }
+
+    /// The name of the test case, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The location of the test case in its source file, if known.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// The input passed to the test function.
+    pub fn input(&self) -> &I {
+        &self.input
+    }
+
+    /// The expected value of the test case.
+    pub fn expected(&self) -> &E {
+        &self.expected
+    }
+
+    /// The value returned by the test function, `None` if it panicked.
+    pub fn result(&self) -> Option<&R> {
+        self.outcome.returned()
+    }
+
+    /// The outcome of the test function.
+    pub fn outcome(&self) -> &Outcome<R> {
+        &self.outcome
+    }
+
+    /// The panic message, if the test function panicked.
+    pub fn panic_message(&self) -> Option<&str> {
+        self.outcome.panic_message()
+    }
+
+    /// The reason the assertion failed, if explained.
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// The differences of the expected value and the result, see [`CaseFailure::with_differences`].
+    pub fn differences(&self) -> &[Difference] {
+        &self.differences
+    }
+
+    /// Decomposes the failure into a `(input, expected, outcome, case_id)` tuple.
+    pub fn into_parts(self) -> (I, E, Outcome<R>, usize) {
+        (self.input, self.expected, self.outcome, self.case_id)
+    }
+}
+
+impl<I, E, R> CaseFailure<I, E, R> {
+    /// Writes `test case {case_id} "{name}" at {location}`, identifying the case in the report.
+    fn fmt_title(&self, f: &mut impl Write) -> fmt::Result {
+        write!(f, "test case {}", self.case_id)?;
+        if let Some(name) = &self.name {
+            write!(f, " {:?}", name)?;
+        }
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the reason of the failure, if explained, indenting all lines of a multi-line reason.
+    fn fmt_reason(&self, f: &mut impl Write) -> fmt::Result {
+        let Some(reason) = &self.reason else {
+            return Ok(());
+        };
+        let mut lines = reason.lines();
+        writeln!(f, "\treason: {}", lines.next().unwrap_or_default())?;
+        for line in lines {
+            writeln!(f, "\t{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+impl<I: Debug, E: Debug, R: Debug> Display for CaseFailure<I, E, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_title(f)?;
+        match &self.outcome {
+            Outcome::Returned(result) => {
+                writeln!(f, ": assertion failed for input `{:#?}`", self.input)?;
+                if self.reason.is_some() {
+                    // the reason explains the failure, dumping both values again is noise
+                    self.fmt_reason(f)?;
+                    return writeln!(f, "\texpected `{:?}`", self.expected);
+                }
+                let expected = debug_string(&self.expected, true)?;
+                let result_text = debug_string(result, true)?;
+                if expected.contains('\n') || result_text.contains('\n') {
+                    writeln!(f, "\tdiff of expected (-) and result (+):")?;
+                    diff::write_unified(f, "\t", &expected, &result_text)?;
+                } else {
+                    write!(f, "\texpected `{}`\n\tresult `{}`\n", expected, result_text)?;
+                }
+                if self.differences.is_empty() {
+                    tree::write_paths(
+                        f,
+                        "\t",
+                        &debug_string(&self.expected, false)?,
+                        &debug_string(result, false)?,
+                    )
+                } else {
+                    tree::write_differences(f, "\t", &self.differences)
+                }
+            }
+            Outcome::Panicked(message) => {
+                writeln!(f, ": test function panicked for input `{:#?}`", self.input)?;
+                self.fmt_reason(f)?;
+                write!(
+                    f,
+                    "\texpected `{:#?}`\n\tpanicked `{}`\n",
+                    self.expected, message
+                )
+            }
+        }
+    }
+}
+
+/// Prints the `Debug` representation of `value`, pretty printed if `pretty` is set.
+fn debug_string(value: &impl Debug, pretty: bool) -> Result<String, fmt::Error> {
+    let mut debug = String::new();
+    if pretty {
+        write!(&mut debug, "{:#?}", value)?;
+    } else {
+        write!(&mut debug, "{:?}", value)?;
+    }
+    Ok(debug)
+}
+
+impl<I, E, R> From<(I, E, R, usize)> for CaseFailure<I, E, R> {
+    fn from((input, expected, result, case_id): (I, E, R, usize)) -> Self {
+        Self::new(case_id, input, expected, result)
+    }
+}
+
+/// A collection of failed test-cases.
+///
+/// Returned by [`collect_fails!`](crate::collect_fails), its `Display` implementation renders the report
+/// printed by [`report_fails`].
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{CaseFailure, FailReport};
+///
+/// let report: FailReport<_, _, _> = vec![CaseFailure::new(2, "hello world!", "hello papa!", "hello mom!")]
+///     .into_iter()
+///     .collect();
+///
+/// assert_eq!(report.len(), 1);
+/// assert_eq!(report.iter().next().map(|fail| fail.case_id()), Some(2));
+/// assert_eq!(report.fails()[0].result(), Some(&"hello mom!"));
+/// assert!(report.to_string().starts_with("One or more assertions failed:\ntest case 2:"));
+/// ```
+///
+/// **Diff of multi-line values:**
+///
+/// When the pretty printed expected value or result span multiple lines, the report shows
+/// their line-by-line diff instead of both values.
+/// ```rust
+/// use tiny_test::{CaseFailure, FailReport};
+///
+/// let report = FailReport::from(vec![CaseFailure::new(1, "1 2 3 5", vec![1, 2, 3, 4], vec![1, 2, 3, 5])]);
+///
+/// assert_eq!(
+///     report.to_string(),
+///     "One or more assertions failed:
+/// test case 1: assertion failed for input `\"1 2 3 5\"`
+/// \tdiff of expected (-) and result (+):
+/// \t@@ -2,5 +2,5 @@
+/// \t      1,
+/// \t      2,
+/// \t      3,
+/// \t-     4,
+/// \t+     5,
+/// \t  ]
+/// \tdiffering paths:
+/// \t  [3]: expected 4, got 5
+///
+/// "
+/// );
+/// ```
+///
+/// **Differing paths:**
+///
+/// The `Debug` representations of the expected value and result are parsed into trees of structs,
+/// tuples, enums, lists and maps, the report lists the paths at which they differ.
+/// ```rust
+/// use tiny_test::{CaseFailure, FailReport};
+///
+/// #[derive(Debug)]
+/// struct Span {
+///     start: usize,
+///     end: usize,
+/// }
+///
+/// let report = FailReport::from(vec![CaseFailure::new(
+///     1,
+///     "x + y",
+///     Ok::<_, ()>(("", vec![Span { start: 0, end: 1 }, Span { start: 4, end: 5 }])),
+///     Ok::<_, ()>(("", vec![Span { start: 0, end: 1 }, Span { start: 4, end: 6 }])),
+/// )]);
+///
+/// assert!(report.to_string().contains("\tdiffering paths:\n\t  .0.1[1].end: expected 5, got 6\n"));
+/// ```
+///
+/// **Failures with a reason:**
+///
+/// When the assertion explains the failure, such as a [matcher](crate::matchers), the report shows the
+/// reason and the expectation on one line instead of the diff and paths.
+/// ```rust
+/// use tiny_test::{collect_fails, matchers::{matches, unordered, Unordered}};
+///
+/// let fails = collect_fails!(
+///     u8,
+///     Unordered<Vec<u8>>,
+///     Vec<u8>,
+///     [1 => unordered(vec![1, 2, 2])],
+///     |_: &u8| vec![2, 3, 1],
+///     matches
+/// );
+///
+/// assert_eq!(
+///     fails.to_string(),
+///     "One or more assertions failed:
+/// test case 1: assertion failed for input `1`
+/// \treason: missing: [2], unexpected: [3]
+/// \texpected `in any order [1, 2, 2]`
+///
+/// "
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct FailReport<I, E, R> {
+    fails: Vec<CaseFailure<I, E, R>>,
+}
+
+impl<I, E, R> FailReport<I, E, R> {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self { fails: Vec::new() }
+    }
+
+    /// Appends a failed test case to the report.
+    pub fn push(&mut self, fail: CaseFailure<I, E, R>) {
+        self.fails.push(fail);
+    }
+
+    /// The number of failed test cases.
+    pub fn len(&self) -> usize {
+        self.fails.len()
+    }
+
+    /// Returns `true` if no test case failed.
+    pub fn is_empty(&self) -> bool {
+        self.fails.is_empty()
+    }
+
+    /// Iterates the failed test cases in the order they were run.
+    pub fn iter(&self) -> std::slice::Iter<'_, CaseFailure<I, E, R>> {
+        self.fails.iter()
+    }
+
+    /// The failed test cases in the order they were run.
+    pub fn fails(&self) -> &[CaseFailure<I, E, R>] {
+        &self.fails
+    }
+
+    /// Consumes the report, returning the failed test cases.
+    pub fn into_fails(self) -> Vec<CaseFailure<I, E, R>> {
+        self.fails
+    }
+}
+
+impl<I, E, R> Default for FailReport<I, E, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Debug, E: Debug, R: Debug> Display for FailReport<I, E, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "One or more assertions failed:")?;
+        let mut message = String::with_capacity(1024);
+        for fail in &self.fails {
+            message.clear();
+            if write!(&mut message, "{}", fail).is_err() {
+                message.clear();
+                fail.fmt_title(&mut message)?;
+                message += ": assertion failed, unable to print message\n";
+            }
+            writeln!(f, "{}", message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the report, as the `Display` implementation.
+///
+/// A test returning `Result<(), Box<dyn Error>>` prints the `Debug` representation of its error.
+impl<I: Debug, E: Debug, R: Debug> Debug for FailReport<I, E, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<I: Debug, E: Debug, R: Debug> std::error::Error for FailReport<I, E, R> {}
+
+impl<I, E, R> FromIterator<CaseFailure<I, E, R>> for FailReport<I, E, R> {
+    fn from_iter<T: IntoIterator<Item = CaseFailure<I, E, R>>>(iter: T) -> Self {
+        Self {
+            fails: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<I, E, R> Extend<CaseFailure<I, E, R>> for FailReport<I, E, R> {
+    fn extend<T: IntoIterator<Item = CaseFailure<I, E, R>>>(&mut self, iter: T) {
+        self.fails.extend(iter)
+    }
+}
+
+impl<I, E, R> IntoIterator for FailReport<I, E, R> {
+    type Item = CaseFailure<I, E, R>;
+    type IntoIter = std::vec::IntoIter<CaseFailure<I, E, R>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fails.into_iter()
+    }
+}
+
+impl<'a, I, E, R> IntoIterator for &'a FailReport<I, E, R> {
+    type Item = &'a CaseFailure<I, E, R>;
+    type IntoIter = std::slice::Iter<'a, CaseFailure<I, E, R>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fails.iter()
+    }
+}
+
+impl<I, E, R> From<Vec<CaseFailure<I, E, R>>> for FailReport<I, E, R> {
+    fn from(fails: Vec<CaseFailure<I, E, R>>) -> Self {
+        Self { fails }
+    }
+}
+
+impl<I, E, R> From<Vec<(I, E, R, usize)>> for FailReport<I, E, R> {
+    fn from(fails: Vec<(I, E, R, usize)>) -> Self {
+        fails.into_iter().map(CaseFailure::from).collect()
+    }
+}
+
+/// Constructs a pretty print report of all failed assertions.
+/// - **This method does not check for plausible input!**
+/// - Panics if `fails.is_empty() == false`.
+///
+/// # Usage
+/// Usually used in combination with `collect_fails`, accepts a [`FailReport`] or
+/// a `Vec` of `(input, expected, result, case_id)` tuples.
+///
+/// **Basic usage:**
+/// ```rust,should_panic
+/// use tiny_test::report_fails;
+///
+/// report_fails(vec![
+///     ("input string", "expected string", "", 1),
+///     ("hello world!", "hello papa!", "hello mom!", 2),
+/// ])
+///
+/// // One or more assertions failed:
+/// // test case 1: assertion failed for input `"input string"`
+/// //         expected `"expected string"`
+/// //         result `""`
+/// //
+/// // test case 2: assertion failed for input `"hello world!"`
+/// //         expected `"hello papa!"`
+/// //         result `"hello mom!"`
+/// //
+/// ```
+pub fn report_fails<I: Debug, E: Debug, R: Debug>(fails: impl Into<FailReport<I, E, R>>) {
+    let report = fails.into();
+    if report.is_empty() {
+        return;
+    }
+    panic!("{}", report);
+}
+
+/// Checks for failed assertions, the non-panicking counterpart of [`report_fails`].
+/// - Returns `Err` with the report if `fails.is_empty() == false`.
+///
+/// # Usage
+/// Usually used in combination with `collect_fails` in tests returning a `Result`.
+///
+/// **Basic usage:**
+/// ```rust
+/// use std::error::Error;
+/// use tiny_test::{check_fails, collect_fails};
+///
+/// fn test_double() -> Result<(), Box<dyn Error>> {
+///     check_fails(collect_fails!(
+///         usize,
+///         usize,
+///         vec![(1, 2), (2, 4)].into_iter(),
+///         |input| input * 2
+///     ))?;
+///     Ok(())
+/// }
+///
+/// test_double().unwrap();
+///
+/// let report = check_fails(vec![("hello world!", "hello papa!", "hello mom!", 2)]).unwrap_err();
+/// assert!(report.to_string().starts_with("One or more assertions failed:\ntest case 2:"));
+/// ```
+pub fn check_fails<I, E, R>(
+    fails: impl Into<FailReport<I, E, R>>,
+) -> Result<(), FailReport<I, E, R>> {
+    let report = fails.into();
+    if report.is_empty() {
+        Ok(())
+    } else {
+        Err(report)
+    }
+}
@@ -0,0 +1,292 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use crate::{
+    check_fails, report_fails, tiny_diff, AssertOutput, Assertion, Case, CaseFailure, Difference,
+    Equal, FailMode, FailReport, IntoCase, Location, Outcome, TinyDiff, TinyEqual,
+};
+
+/// A table of test-cases in the format `(input, expected)` or `(name, input, expected)`.
+///
+/// The builder counterpart of [`collect_fails!`](crate::collect_fails), the input, expected
+/// and result types are inferred from the cases and the test function.
+///
+/// # Examples
+/// **Basic usage:**
+/// ```rust
+/// use tiny_test::TestTable;
+///
+/// fn first_word(input: &str) -> &str {
+///     input.split(' ').next().unwrap_or_default()
+/// }
+///
+/// TestTable::new(vec![("hello world", "hello"), ("", ""), ("tiny test", "tiny")])
+///     .run(|input| first_word(input))
+///     .report();
+/// ```
+///
+/// **Custom assertion:**
+/// ```rust
+/// use tiny_test::{FailMode, TestTable};
+///
+/// let fails = TestTable::new(vec![(2, 1..5), (3, 4..6), (0, 1..2)])
+///     .mode(FailMode::Collect)
+///     .run(|input| input + 2)
+///     .assert_with(|output, expected| expected.contains(output))
+///     .collect();
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(fails.fails()[0].case_id(), 3);
+/// ```
+///
+/// **Named test-cases:**
+/// ```rust
+/// use tiny_test::TestTable;
+///
+/// let fails = TestTable::new(vec![
+///     ("empty path", "", 0),
+///     ("trailing slash", "a/", 1),
+/// ])
+/// .run(|input| input.split('/').count())
+/// .collect();
+///
+/// assert_eq!(fails.len(), 2);
+/// assert_eq!(fails.fails()[1].name(), Some("trailing slash"));
+/// assert!(fails.to_string().contains("test case 2 \"trailing slash\": assertion failed"));
+/// ```
+///
+/// **Test-cases with source locations:**
+/// ```rust
+/// use tiny_test::TestTable;
+///
+/// let fails = TestTable::default()
+///     .case("a/b", 3)
+///     .named_case("trailing slash", "a/", 1)
+///     .run(|input| input.split('/').count())
+///     .collect();
+///
+/// assert_eq!(fails.fails()[0].location().map(|location| location.line()), Some(line!() - 5));
+/// assert!(fails.to_string().contains(&format!("test case 2 \"trailing slash\" at {}:", file!())));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestTable<I, E> {
+    cases: Vec<Case<I, E>>,
+    mode: Option<FailMode>,
+}
+
+impl<I, E> TestTable<I, E> {
+    /// Creates a table from the test-cases, see [`IntoCase`].
+    pub fn new(cases: impl IntoIterator<Item = impl IntoCase<I, E>>) -> Self {
+        Self {
+            cases: cases.into_iter().map(IntoCase::into_case).collect(),
+            mode: None,
+        }
+    }
+
+    /// Appends a test-case, recording the source location of the call.
+    #[track_caller]
+    pub fn case(mut self, input: I, expected: E) -> Self {
+        self.cases
+            .push(Case::new(input, expected).with_location(Location::caller()));
+        self
+    }
+
+    /// Appends a named test-case, recording the source location of the call.
+    #[track_caller]
+    pub fn named_case(mut self, name: impl Into<String>, input: I, expected: E) -> Self {
+        self.cases
+            .push(Case::named(name, input, expected).with_location(Location::caller()));
+        self
+    }
+
+    /// Selects the [`FailMode`] of the table, instead of the mode of the environment.
+    pub fn mode(mut self, mode: FailMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// The number of test-cases in the table.
+    pub fn len(&self) -> usize {
+        self.cases.len()
+    }
+
+    /// Returns `true` if the table has no test-cases.
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+
+    /// Sets the function to test, comparing its results to the expected values for equality.
+    pub fn run<R, T>(self, test: T) -> TestRun<I, E, R, T, Equal>
+    where
+        T: FnMut(&I) -> R,
+    {
+        TestRun {
+            table: self,
+            test,
+            assert: Equal,
+            differ: no_differences,
+            result: PhantomData,
+        }
+    }
+}
+
+impl<I, E> Default for TestTable<I, E> {
+    fn default() -> Self {
+        Self {
+            cases: Vec::new(),
+            mode: None,
+        }
+    }
+}
+
+/// A function listing the differences of an expected value and a result.
+type DiffFn<E, R> = fn(&E, &R) -> Vec<Difference>;
+
+/// Lists no differences, leaving the report to parse the `Debug` representations.
+fn no_differences<E, R>(_: &E, _: &R) -> Vec<Difference> {
+    Vec::new()
+}
+
+/// A [`TestTable`] with its test function, created by [`TestTable::run`].
+///
+/// No test-case is run until the failures are collected or reported.
+pub struct TestRun<I, E, R, T, A, D = DiffFn<E, R>> {
+    table: TestTable<I, E>,
+    test: T,
+    assert: A,
+    differ: D,
+    result: PhantomData<fn() -> R>,
+}
+
+impl<I, E, R, T, A, D> TestRun<I, E, R, T, A, D> {
+    /// Replaces the assertion, a function of the result and the expected value returning
+    /// an [`AssertOutput`], such as `bool`, `Result<(), String>` or `Option<String>`.
+    pub fn assert_with<F, O>(self, assert: F) -> TestRun<I, E, R, T, F, D>
+    where
+        F: FnMut(&R, &E) -> O,
+        O: AssertOutput,
+    {
+        TestRun {
+            table: self.table,
+            test: self.test,
+            assert,
+            differ: self.differ,
+            result: PhantomData,
+        }
+    }
+
+    /// Replaces the assertion with an [`Assertion`], such as [`Matches`](crate::matchers::Matches).
+    pub fn assert_by<B>(self, assert: B) -> TestRun<I, E, R, T, B, D>
+    where
+        B: Assertion<R, E>,
+    {
+        TestRun {
+            table: self.table,
+            test: self.test,
+            assert,
+            differ: self.differ,
+            result: PhantomData,
+        }
+    }
+
+    /// Sets the function listing the differences of the expected value and result of failed test-cases.
+    pub fn diff_with<F>(self, differ: F) -> TestRun<I, E, R, T, A, F>
+    where
+        F: FnMut(&E, &R) -> Vec<Difference>,
+    {
+        TestRun {
+            table: self.table,
+            test: self.test,
+            assert: self.assert,
+            differ,
+            result: PhantomData,
+        }
+    }
+
+    /// Selects the [`FailMode`] of the table, instead of the mode of the environment.
+    pub fn mode(mut self, mode: FailMode) -> Self {
+        self.table.mode = Some(mode);
+        self
+    }
+}
+
+impl<I, R: TinyDiff, T, A, D> TestRun<I, R, R, T, A, D> {
+    /// Compares the results and expected values using [`TinyDiff`], listing the differences of
+    /// failed test-cases.
+    ///
+    /// Replaces the assertion with [`TinyEqual`], so fields excluded from the comparison do not fail
+    /// a test-case. A custom assertion is kept by `diff_with(TinyDiff::differences)` instead.
+    pub fn tiny_diff(self) -> TestRun<I, R, R, T, TinyEqual, DiffFn<R, R>> {
+        self.diff_with(tiny_diff::differences_or_whole as DiffFn<R, R>)
+            .assert_by(TinyEqual)
+    }
+}
+
+impl<I, E, R, T, A, D> TestRun<I, E, R, T, A, D>
+where
+    T: FnMut(&I) -> R,
+    A: Assertion<R, E>,
+    D: FnMut(&E, &R) -> Vec<Difference>,
+{
+    /// Runs the test-cases, collecting the failed ones according to the [`FailMode`].
+    pub fn collect(mut self) -> FailReport<I, E, R>
+    where
+        I: Debug,
+        E: Debug,
+        R: Debug,
+    {
+        let mode = FailMode::resolve(self.table.mode);
+        let mut fails = FailReport::new();
+        for (case_id, case) in self.table.cases.into_iter().enumerate() {
+            let test = &mut self.test;
+            let outcome = Outcome::catch(|| test(case.input()));
+            let verdict = match &outcome {
+                Outcome::Returned(result) => self.assert.assert(result, case.expected()),
+                Outcome::Panicked(message) => self.assert.assert_panic(message, case.expected()),
+            };
+            let Err(reason) = verdict else {
+                continue;
+            };
+            let differences = match &outcome {
+                Outcome::Returned(result) => (self.differ)(case.expected(), result),
+                Outcome::Panicked(_) => Vec::new(),
+            };
+            let fail =
+                CaseFailure::from_case(case_id + 1, case, outcome).with_differences(differences);
+            fails.push(match reason {
+                Some(reason) => fail.with_reason(reason),
+                None => fail,
+            });
+            match mode {
+                FailMode::Collect => {}
+                FailMode::PanicFirst => panic!("{}", fails),
+                FailMode::BreakOnFailure => break,
+            }
+        }
+        fails
+    }
+
+    /// Runs the test-cases, returning the report of all failed assertions as error.
+    ///
+    /// See [`check_fails`].
+    pub fn check(self) -> Result<(), FailReport<I, E, R>>
+    where
+        I: Debug,
+        E: Debug,
+        R: Debug,
+    {
+        check_fails(self.collect())
+    }
+
+    /// Runs the test-cases, panicking with the report of all failed assertions.
+    ///
+    /// See [`report_fails`].
+    pub fn report(self)
+    where
+        I: Debug,
+        E: Debug,
+        R: Debug,
+    {
+        report_fails(self.collect())
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+
+/// A difference between the expected value and the result of a test-case.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Difference {
+    path: String,
+    message: String,
+}
+
+impl Difference {
+    /// Creates a difference at `path`, such as `.1.children[3].span`, described by `message`.
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates a difference at `path` of the values `expected` and `result`.
+    pub fn mismatch(path: impl Into<String>, expected: &dyn Debug, result: &dyn Debug) -> Self {
+        Self::new(path, format!("expected {:?}, got {:?}", expected, result))
+    }
+
+    /// The path to the differing value, empty if the values differ as a whole.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Describes what differs at the path.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Joins the `differences` into one reason, omitting the empty path of a difference of the values as a whole.
+pub(crate) fn join_differences(differences: &[Difference]) -> String {
+    let differences: Vec<String> = differences
+        .iter()
+        .map(|difference| match difference.path() {
+            "" => difference.message().to_owned(),
+            _ => difference.to_string(),
+        })
+        .collect();
+    differences.join("; ")
+}
+
+/// Field-by-field comparison of an expected value and a result.
+///
+/// Usually derived with `#[derive(TinyDiff)]` of the `derive` feature. The derived implementation
+/// compares all fields, except those marked `#[tiny(ignore)]`. Fields marked
+/// `#[tiny(compare_with = "path::to::fn")]` are compared by a function `fn(&T, &T) -> bool`.
+///
+/// When the result type of a [`collect_fails!`](crate::collect_fails) table implements `TinyDiff`,
+/// a result without differences passes the default assertion, see [`TinyEqual`](crate::TinyEqual),
+/// and the report lists the differences instead of those found by parsing the `Debug` representation.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{Difference, TinyDiff};
+///
+/// let differences = vec![Some(1), None, Some(3)].differences(&vec![Some(1), Some(2), Some(4)]);
+///
+/// assert_eq!(
+///     differences,
+///     vec![
+///         Difference::new("[1]", "expected None, got Some(2)"),
+///         Difference::new("[2].0", "expected 3, got 4"),
+///     ]
+/// );
+/// ```
+pub trait TinyDiff: Debug {
+    /// Appends the differences of `self`, the expected value, and `result` at `path` to `differences`.
+    fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>);
+
+    /// The differences of `self`, the expected value, and `result`.
+    fn differences(&self, result: &Self) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        self.tiny_diff(result, "", &mut differences);
+        differences
+    }
+}
+
+/// The differences of `expected` and `result`, or a difference of the values as a whole if there are
+/// none, so that a result failing a custom assertion is not explained by its fields excluded from the
+/// comparison.
+pub(crate) fn differences_or_whole<T: TinyDiff>(expected: &T, result: &T) -> Vec<Difference> {
+    let differences = expected.differences(result);
+    if differences.is_empty() {
+        vec![Difference::new("", "equal in the compared fields")]
+    } else {
+        differences
+    }
+}
+
+macro_rules! impl_leaf {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl TinyDiff for $ty {
+                fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>) {
+                    if self != result {
+                        differences.push(Difference::mismatch(path, &self, &result));
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_leaf!(
+    (),
+    bool,
+    char,
+    str,
+    String,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+);
+
+impl<T: TinyDiff + ?Sized> TinyDiff for &T {
+    fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>) {
+        (**self).tiny_diff(*result, path, differences)
+    }
+}
+
+impl<T: TinyDiff + ?Sized> TinyDiff for Box<T> {
+    fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>) {
+        (**self).tiny_diff(result, path, differences)
+    }
+}
+
+impl<T: TinyDiff> TinyDiff for Option<T> {
+    fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>) {
+        match (self, result) {
+            (Some(expected), Some(result)) => {
+                expected.tiny_diff(result, &format!("{}.0", path), differences)
+            }
+            (None, None) => {}
+            _ => differences.push(Difference::mismatch(path, self, result)),
+        }
+    }
+}
+
+impl<T: TinyDiff, E: TinyDiff> TinyDiff for Result<T, E> {
+    fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>) {
+        match (self, result) {
+            (Ok(expected), Ok(result)) => {
+                expected.tiny_diff(result, &format!("{}.0", path), differences)
+            }
+            (Err(expected), Err(result)) => {
+                expected.tiny_diff(result, &format!("{}.0", path), differences)
+            }
+            _ => differences.push(Difference::mismatch(path, self, result)),
+        }
+    }
+}
+
+impl<T: TinyDiff> TinyDiff for [T] {
+    fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>) {
+        for (index, (expected, result)) in self.iter().zip(result).enumerate() {
+            expected.tiny_diff(result, &format!("{}[{}]", path, index), differences);
+        }
+        if self.len() != result.len() {
+            differences.push(Difference::new(
+                path,
+                format!("expected {} elements, got {}", self.len(), result.len()),
+            ));
+        }
+    }
+}
+
+impl<T: TinyDiff, const N: usize> TinyDiff for [T; N] {
+    fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>) {
+        self[..].tiny_diff(&result[..], path, differences)
+    }
+}
+
+impl<T: TinyDiff> TinyDiff for Vec<T> {
+    fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>) {
+        self[..].tiny_diff(&result[..], path, differences)
+    }
+}
+
+/// Compares the entries of two maps with equal keys, reporting missing and unexpected keys.
+fn diff_entries<'a, K: Debug + 'a, V: TinyDiff + 'a>(
+    expected: impl Iterator<Item = (&'a K, &'a V)>,
+    result: impl Iterator<Item = (&'a K, &'a V)>,
+    get_expected: impl Fn(&K) -> Option<&'a V>,
+    get_result: impl Fn(&K) -> Option<&'a V>,
+    path: &str,
+    differences: &mut Vec<Difference>,
+) {
+    for (key, expected) in expected {
+        let path = format!("{}[{:?}]", path, key);
+        match get_result(key) {
+            Some(result) => expected.tiny_diff(result, &path, differences),
+            None => differences.push(Difference::new(
+                path,
+                format!("expected {:?}, key is missing", expected),
+            )),
+        }
+    }
+    for (key, result) in result {
+        if get_expected(key).is_none() {
+            differences.push(Difference::new(
+                format!("{}[{:?}]", path, key),
+                format!("unexpected key, got {:?}", result),
+            ));
+        }
+    }
+}
+
+impl<K: Ord + Debug, V: TinyDiff> TinyDiff for BTreeMap<K, V> {
+    fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>) {
+        diff_entries(
+            self.iter(),
+            result.iter(),
+            |key| self.get(key),
+            |key| result.get(key),
+            path,
+            differences,
+        )
+    }
+}
+
+impl<K: Eq + Hash + Debug, V: TinyDiff, S: std::hash::BuildHasher> TinyDiff for HashMap<K, V, S> {
+    fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>) {
+        diff_entries(
+            self.iter(),
+            result.iter(),
+            |key| self.get(key),
+            |key| result.get(key),
+            path,
+            differences,
+        )
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name: TinyDiff),+> TinyDiff for ($($name,)+) {
+            fn tiny_diff(&self, result: &Self, path: &str, differences: &mut Vec<Difference>) {
+                $(self.$index.tiny_diff(&result.$index, &format!("{}.{}", path, $index), differences);)+
+            }
+        }
+    };
+}
+
+impl_tuple!(A 0);
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+#[doc(hidden)]
+pub mod __private {
+    //! Autoref specialization selecting [`TinyDiff`] for the verdict and differences of a table, if implemented.
+    use super::{Difference, TinyDiff};
+
+    pub struct DiffProbe<'a, E, R>(pub &'a E, pub &'a R);
+
+    pub trait ViaTinyDiff {
+        fn tiny_equal(&self) -> bool;
+
+        fn tiny_differences(&self) -> Vec<Difference>;
+    }
+
+    impl<T: TinyDiff> ViaTinyDiff for DiffProbe<'_, T, T> {
+        fn tiny_equal(&self) -> bool {
+            self.0.differences(self.1).is_empty()
+        }
+
+        fn tiny_differences(&self) -> Vec<Difference> {
+            super::differences_or_whole(self.0, self.1)
+        }
+    }
+
+    pub trait ViaPartialEq {
+        fn tiny_equal(&self) -> bool;
+    }
+
+    impl<E, R: PartialEq<E>> ViaPartialEq for &DiffProbe<'_, E, R> {
+        fn tiny_equal(&self) -> bool {
+            self.1 == self.0
+        }
+    }
+
+    pub trait ViaDebug {
+        fn tiny_differences(&self) -> Vec<Difference>;
+    }
+
+    impl<E, R> ViaDebug for &DiffProbe<'_, E, R> {
+        fn tiny_differences(&self) -> Vec<Difference> {
+            Vec::new()
+        }
+    }
+}
@@ -0,0 +1,563 @@
+use std::fmt::{self, Write};
+
+use crate::Difference;
+
+/// The maximum number of differing paths listed in a report.
+const MAX_PATHS: usize = 8;
+
+/// A value parsed from its compact `Debug` representation.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Node<'a> {
+    /// The `Debug` text of the value.
+    text: &'a str,
+    kind: Kind<'a>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind<'a> {
+    /// A value without structure, such as a number, string or unit variant.
+    Atom,
+    /// A struct or struct variant `Name { field: value }`, possibly non-exhaustive `Name { field: value, .. }`.
+    Struct(&'a str, Vec<(&'a str, Node<'a>)>, bool),
+    /// A tuple `(a, b)` or tuple struct or variant `Name(a, b)`.
+    Tuple(Option<&'a str>, Vec<Node<'a>>),
+    /// A list `[a, b]` or set `{a, b}`.
+    List(Vec<Node<'a>>),
+    /// A map `{key: value}`.
+    Map(Vec<(Node<'a>, Node<'a>)>),
+}
+
+/// Parses the compact `Debug` representation of a value, `None` if the representation is not understood.
+pub(crate) fn parse(text: &str) -> Option<Node<'_>> {
+    let mut parser = Parser { text, pos: 0 };
+    let node = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos == text.len() {
+        Some(node)
+    } else {
+        None
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes `token` after optional whitespace.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.text[self.pos..].starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Option<Node<'a>> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let kind = match self.peek()? {
+            '(' => {
+                self.pos += 1;
+                Kind::Tuple(None, self.sequence(')')?)
+            }
+            '[' => {
+                self.pos += 1;
+                Kind::List(self.sequence(']')?)
+            }
+            '{' => {
+                self.pos += 1;
+                self.map_or_set()?
+            }
+            '"' | '\'' => {
+                self.quoted()?;
+                Kind::Atom
+            }
+            _ => {
+                let name = self.atom()?;
+                let before_body = self.pos;
+                if self.eat("{") {
+                    let (fields, non_exhaustive) = self.fields()?;
+                    Kind::Struct(name, fields, non_exhaustive)
+                } else if self.text[before_body..].starts_with('(') {
+                    self.pos = before_body + 1;
+                    Kind::Tuple(Some(name), self.sequence(')')?)
+                } else {
+                    self.pos = before_body;
+                    Kind::Atom
+                }
+            }
+        };
+        Some(Node {
+            text: &self.text[start..self.pos],
+            kind,
+        })
+    }
+
+    /// Parses comma separated values up to the `close` delimiter.
+    fn sequence(&mut self, close: char) -> Option<Vec<Node<'a>>> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek()? == close {
+                self.pos += close.len_utf8();
+                return Some(items);
+            }
+            if !items.is_empty() && !self.eat(",") {
+                return None;
+            }
+            self.skip_whitespace();
+            if self.peek()? == close {
+                continue;
+            }
+            items.push(self.value()?);
+        }
+    }
+
+    /// Parses the `field: value` pairs of a struct up to the closing brace, and whether they end with `..`.
+    fn fields(&mut self) -> Option<(Vec<(&'a str, Node<'a>)>, bool)> {
+        let mut fields = Vec::new();
+        let mut non_exhaustive = false;
+        loop {
+            if self.eat("}") {
+                return Some((fields, non_exhaustive));
+            }
+            if (!fields.is_empty() || non_exhaustive) && !self.eat(",") {
+                return None;
+            }
+            if self.eat("}") {
+                return Some((fields, non_exhaustive));
+            }
+            if self.eat("..") {
+                non_exhaustive = true;
+                continue;
+            }
+            self.skip_whitespace();
+            let name = self.atom()?;
+            if !self.eat(":") {
+                return None;
+            }
+            fields.push((name, self.value()?));
+        }
+    }
+
+    /// Parses the entries of a map or set up to the closing brace.
+    fn map_or_set(&mut self) -> Option<Kind<'a>> {
+        let mut entries = Vec::new();
+        let mut items = Vec::new();
+        loop {
+            if self.eat("}") {
+                return Some(if items.is_empty() {
+                    Kind::Map(entries)
+                } else {
+                    Kind::List(items)
+                });
+            }
+            let first = entries.is_empty() && items.is_empty();
+            if !first && !self.eat(",") {
+                return None;
+            }
+            if self.eat("}") {
+                continue;
+            }
+            let key = self.value()?;
+            if self.eat(":") {
+                if !items.is_empty() {
+                    return None;
+                }
+                entries.push((key, self.value()?));
+            } else {
+                if !entries.is_empty() {
+                    return None;
+                }
+                items.push(key);
+            }
+        }
+    }
+
+    /// Parses a string or character literal.
+    fn quoted(&mut self) -> Option<()> {
+        let quote = self.peek()?;
+        let mut chars = self.text[self.pos + 1..].char_indices();
+        while let Some((index, c)) = chars.next() {
+            if c == '\\' {
+                chars.next();
+            } else if c == quote {
+                self.pos += index + 2;
+                return Some(());
+            }
+        }
+        None
+    }
+
+    /// Parses an identifier, path or literal, such as `Some`, `Kind::A`, `-1.5e3` or `0..4`.
+    fn atom(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        let rest = &self.text[start..];
+        let mut end = 0;
+        let mut chars = rest.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                ':' if rest[index + 1..].starts_with(':') => {
+                    chars.next();
+                }
+                '{' | '}' | '(' | ')' | '[' | ']' | ',' | ':' | '"' | '\'' => break,
+                c if c.is_whitespace() => break,
+                _ => {}
+            }
+            end = chars.peek().map_or(rest.len(), |(index, _)| *index);
+        }
+        if end == 0 {
+            return None;
+        }
+        self.pos += end;
+        Some(&rest[..end])
+    }
+}
+
+impl<'a> Node<'a> {
+    /// The `Debug` text of the value.
+    pub(crate) fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The value at `path`, such as `.span.end`, `.0`, `[3]` or `["key"]`, relative to `self`.
+    ///
+    /// The leading `.` of a path starting with a field may be omitted. Returns the path to the first
+    /// missing value as error.
+    pub(crate) fn get(&self, path: &str) -> Result<&Node<'a>, String> {
+        if !path.is_empty() && !path.starts_with(['.', '[']) {
+            return self
+                .get(&format!(".{}", path))
+                .map_err(|missing| missing[1..].to_owned());
+        }
+        let mut node = self;
+        let mut rest = path;
+        while !rest.is_empty() {
+            let (segment, next) = split_segment(rest).ok_or_else(|| path.to_owned())?;
+            let visited = &path[..path.len() - next.len()];
+            node = match (&node.kind, segment) {
+                (Kind::Struct(_, fields, _), Segment::Field(field)) => fields
+                    .iter()
+                    .find(|(name, _)| *name == field)
+                    .map(|(_, node)| node),
+                (Kind::Tuple(_, items), Segment::Field(index)) => {
+                    index.parse().ok().and_then(|index: usize| items.get(index))
+                }
+                (Kind::List(items), Segment::Key(index)) => {
+                    index.parse().ok().and_then(|index: usize| items.get(index))
+                }
+                (Kind::Map(entries), Segment::Key(key)) => entries
+                    .iter()
+                    .find(|(other, _)| other.text == key)
+                    .map(|(_, node)| node),
+                _ => None,
+            }
+            .ok_or_else(|| visited.to_owned())?;
+            rest = next;
+        }
+        Ok(node)
+    }
+}
+
+/// A segment of a path to a value.
+enum Segment<'p> {
+    /// A struct field or tuple index `.name`.
+    Field(&'p str),
+    /// A list index or map key `[key]`.
+    Key(&'p str),
+}
+
+/// Splits the first segment off a path, `None` if the path is malformed.
+fn split_segment(path: &str) -> Option<(Segment<'_>, &str)> {
+    if let Some(rest) = path.strip_prefix('.') {
+        let end = rest.find(['.', '[']).unwrap_or(rest.len());
+        (end > 0).then(|| (Segment::Field(&rest[..end]), &rest[end..]))
+    } else if let Some(rest) = path.strip_prefix('[') {
+        // the key ends at the first closing bracket outside a string or character literal
+        let mut quote = None;
+        let mut chars = rest.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match (quote, c) {
+                (Some(_), '\\') => {
+                    chars.next();
+                }
+                (Some(q), c) if c == q => quote = None,
+                (None, '"' | '\'') => quote = Some(c),
+                (None, ']') => return Some((Segment::Key(&rest[..index]), &rest[index + 1..])),
+                _ => {}
+            }
+        }
+        None
+    } else {
+        None
+    }
+}
+
+/// Matches a parsed value against a parsed pattern, collecting the paths at which they differ.
+///
+/// The pattern is a `Debug` representation, in which `_` matches any value, structs ending with `..`
+/// match structs with additional fields, and tuples and lists ending with `..` match longer ones.
+pub(crate) fn match_pattern(pattern: &Node<'_>, value: &Node<'_>) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    match_node(&mut String::new(), pattern, value, &mut differences);
+    differences
+}
+
+fn match_node(
+    path: &mut String,
+    pattern: &Node<'_>,
+    value: &Node<'_>,
+    differences: &mut Vec<Difference>,
+) {
+    if pattern.text == "_" || pattern.text == value.text {
+        return;
+    }
+    let len = path.len();
+    match (&pattern.kind, &value.kind) {
+        (
+            Kind::Struct(pattern_name, pattern_fields, non_exhaustive),
+            Kind::Struct(value_name, value_fields, _),
+        ) if pattern_name == value_name => {
+            for (field, pattern) in pattern_fields {
+                path.push('.');
+                path.push_str(field);
+                match value_fields.iter().find(|(name, _)| name == field) {
+                    Some((_, value)) => match_node(path, pattern, value, differences),
+                    None => differences.push(Difference::new(
+                        path.clone(),
+                        format!("expected {}, field is missing", pattern.text),
+                    )),
+                }
+                path.truncate(len);
+            }
+            if !non_exhaustive {
+                for (field, value) in value_fields {
+                    if pattern_fields.iter().all(|(name, _)| name != field) {
+                        differences.push(Difference::new(
+                            format!("{}.{}", path, field),
+                            format!("unexpected field, got {}", value.text),
+                        ));
+                    }
+                }
+            }
+        }
+        (Kind::Tuple(pattern_name, pattern_items), Kind::Tuple(value_name, value_items))
+            if pattern_name == value_name =>
+        {
+            match_items(path, ".", "", pattern_items, value_items, differences)
+        }
+        (Kind::List(pattern_items), Kind::List(value_items)) => {
+            match_items(path, "[", "]", pattern_items, value_items, differences)
+        }
+        (Kind::Map(pattern_entries), Kind::Map(value_entries)) => {
+            for (key, pattern) in pattern_entries {
+                write!(path, "[{}]", key.text).expect("writing to a String never fails");
+                match value_entries
+                    .iter()
+                    .find(|(other, _)| other.text == key.text)
+                {
+                    Some((_, value)) => match_node(path, pattern, value, differences),
+                    None => differences.push(Difference::new(
+                        path.clone(),
+                        format!("expected {}, key is missing", pattern.text),
+                    )),
+                }
+                path.truncate(len);
+            }
+        }
+        _ => differences.push(Difference::new(
+            path.clone(),
+            format!("expected {}, got {}", pattern.text, value.text),
+        )),
+    }
+}
+
+/// Matches the items of a tuple or list, a trailing `..` in the pattern matches any remaining items.
+fn match_items(
+    path: &mut String,
+    open: &str,
+    close: &str,
+    pattern_items: &[Node<'_>],
+    value_items: &[Node<'_>],
+    differences: &mut Vec<Difference>,
+) {
+    let (pattern_items, open_ended) = match pattern_items.split_last() {
+        Some((last, items)) if last.text == ".." => (items, true),
+        _ => (pattern_items, false),
+    };
+    let len = path.len();
+    for (index, (pattern, value)) in pattern_items.iter().zip(value_items).enumerate() {
+        write!(path, "{}{}{}", open, index, close).expect("writing to a String never fails");
+        match_node(path, pattern, value, differences);
+        path.truncate(len);
+    }
+    if value_items.len() < pattern_items.len()
+        || (!open_ended && value_items.len() > pattern_items.len())
+    {
+        differences.push(Difference::new(
+            path.clone(),
+            format!(
+                "expected {}{} elements, got {}",
+                if open_ended { "at least " } else { "" },
+                pattern_items.len(),
+                value_items.len()
+            ),
+        ));
+    }
+}
+
+/// Compares the parsed expected value and the result, collecting the paths at which they differ.
+pub(crate) fn differences(expected: &Node<'_>, result: &Node<'_>) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    compare(&mut String::new(), expected, result, &mut differences);
+    differences
+}
+
+fn compare(
+    path: &mut String,
+    expected: &Node<'_>,
+    result: &Node<'_>,
+    differences: &mut Vec<Difference>,
+) {
+    if expected.text == result.text {
+        return;
+    }
+    let len = path.len();
+    match (&expected.kind, &result.kind) {
+        (
+            Kind::Struct(expected_name, expected_fields, _),
+            Kind::Struct(result_name, result_fields, _),
+        ) if expected_name == result_name => {
+            for (field, expected) in expected_fields {
+                path.push('.');
+                path.push_str(field);
+                match result_fields.iter().find(|(name, _)| name == field) {
+                    Some((_, result)) => compare(path, expected, result, differences),
+                    None => differences.push(Difference::new(
+                        path.clone(),
+                        format!("expected {}, field is missing", expected.text),
+                    )),
+                }
+                path.truncate(len);
+            }
+            for (field, result) in result_fields {
+                if expected_fields.iter().all(|(name, _)| name != field) {
+                    differences.push(Difference::new(
+                        format!("{}.{}", path, field),
+                        format!("unexpected field, got {}", result.text),
+                    ));
+                }
+            }
+        }
+        (Kind::Tuple(expected_name, expected_items), Kind::Tuple(result_name, result_items))
+            if expected_name == result_name && expected_items.len() == result_items.len() =>
+        {
+            for (index, (expected, result)) in expected_items.iter().zip(result_items).enumerate() {
+                write!(path, ".{}", index).expect("writing to a String never fails");
+                compare(path, expected, result, differences);
+                path.truncate(len);
+            }
+        }
+        (Kind::List(expected_items), Kind::List(result_items)) => {
+            for (index, (expected, result)) in expected_items.iter().zip(result_items).enumerate() {
+                write!(path, "[{}]", index).expect("writing to a String never fails");
+                compare(path, expected, result, differences);
+                path.truncate(len);
+            }
+            if expected_items.len() != result_items.len() {
+                differences.push(Difference::new(
+                    path.clone(),
+                    format!(
+                        "expected {} elements, got {}",
+                        expected_items.len(),
+                        result_items.len()
+                    ),
+                ));
+            }
+        }
+        (Kind::Map(expected_entries), Kind::Map(result_entries)) => {
+            for (key, expected) in expected_entries {
+                write!(path, "[{}]", key.text).expect("writing to a String never fails");
+                match result_entries
+                    .iter()
+                    .find(|(other, _)| other.text == key.text)
+                {
+                    Some((_, result)) => compare(path, expected, result, differences),
+                    None => differences.push(Difference::new(
+                        path.clone(),
+                        format!("expected {}, key is missing", expected.text),
+                    )),
+                }
+                path.truncate(len);
+            }
+            for (key, result) in result_entries {
+                if expected_entries
+                    .iter()
+                    .all(|(other, _)| other.text != key.text)
+                {
+                    differences.push(Difference::new(
+                        format!("{}[{}]", path, key.text),
+                        format!("unexpected key, got {}", result.text),
+                    ));
+                }
+            }
+        }
+        _ => differences.push(Difference::new(
+            path.clone(),
+            format!("expected {}, got {}", expected.text, result.text),
+        )),
+    }
+}
+
+/// Writes the paths at which the compact `Debug` representations of `expected` and `result` differ,
+/// each line prefixed with `indent`.
+///
+/// Nothing is written if either representation cannot be parsed.
+pub(crate) fn write_paths(
+    f: &mut impl Write,
+    indent: &str,
+    expected: &str,
+    result: &str,
+) -> fmt::Result {
+    let (Some(expected), Some(result)) = (parse(expected), parse(result)) else {
+        return Ok(());
+    };
+    write_differences(f, indent, &differences(&expected, &result))
+}
+
+/// Writes the paths of the `differences`, each line prefixed with `indent`.
+///
+/// Nothing is written if the values only differ as a whole.
+pub(crate) fn write_differences(
+    f: &mut impl Write,
+    indent: &str,
+    differences: &[Difference],
+) -> fmt::Result {
+    if differences
+        .iter()
+        .all(|difference| difference.path().is_empty())
+    {
+        return Ok(());
+    }
+    writeln!(f, "{}differing paths:", indent)?;
+    for difference in differences.iter().take(MAX_PATHS) {
+        writeln!(f, "{}  {}", indent, difference)?;
+    }
+    if differences.len() > MAX_PATHS {
+        writeln!(f, "{}  and {} more", indent, differences.len() - MAX_PATHS)?;
+    }
+    Ok(())
+}
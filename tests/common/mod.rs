@@ -0,0 +1,30 @@
+//! Helpers shared by the tests of the loaders.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A temporary file, deleted when dropped.
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Loads `text` with `load` from a new temporary file named after `name`, such as `rows.json`, and
+/// deletes the file.
+pub fn load<T>(name: &str, text: &str, load: impl FnOnce(PathBuf) -> T) -> T {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let count = COUNT.fetch_add(1, Ordering::Relaxed);
+    let (stem, extension) = name.rsplit_once('.').expect("the name has an extension");
+    let file = TempFile(std::env::temp_dir().join(format!(
+        "tiny_test_{}_{}_{}.{}",
+        stem,
+        std::process::id(),
+        count,
+        extension
+    )));
+    std::fs::write(&file.0, text).unwrap();
+    load(file.0.clone())
+}
@@ -0,0 +1,28 @@
+use tiny_test::matchers::{displays, Matcher};
+
+#[test]
+fn small_changes_are_diffed_line_by_line() {
+    let reason = displays("a\nb\nc\nd").check("a\nx\nc\nd").unwrap_err();
+    assert_eq!(
+        reason,
+        "diff of expected (-) and result (+):\n  a\n- b\n+ x\n  c\n  d\n"
+    );
+}
+
+#[test]
+fn large_changes_are_shown_as_removed_then_added_blocks() {
+    let expected: String = (0..5000).map(|n| format!("e{}\n", n)).collect();
+    let result: String = (0..5000).map(|n| format!("r{}\n", n)).collect();
+    let reason = displays(&expected).check(&result).unwrap_err();
+    let removed = reason.find("- e4999\n").unwrap();
+    let added = reason.find("+ r0\n").unwrap();
+    assert!(removed < added);
+    assert_eq!(
+        reason.lines().filter(|line| line.starts_with("- ")).count(),
+        5000
+    );
+    assert_eq!(
+        reason.lines().filter(|line| line.starts_with("+ ")).count(),
+        5000
+    );
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "json")]
+
+mod common;
+
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use tiny_test::{cases_from_json, Case, LoadError};
+
+fn load(name: &str, text: &str) -> Result<Vec<Case<String, i64>>, LoadError> {
+    common::load(&format!("{}.json", name), text, cases_from_json)
+}
+
+/// The names and lines of `cases`.
+fn names_lines<I, E>(cases: &[Case<I, E>]) -> Vec<(Option<&str>, u32)> {
+    cases
+        .iter()
+        .map(|case| (case.name(), case.location().unwrap().line()))
+        .collect()
+}
+
+#[test]
+fn rows_are_located_at_their_line() {
+    let text = r#"[
+  {"name": "first", "input": "a, ]", "expected": 1},
+  {"input": "é 😀", "expected": 2}, {"name": null, "input": "b", "expected": 3},
+
+  {
+    "name": "last",
+    "input": "\"c\",",
+    "expected": 4
+  }
+]"#;
+    let cases = load("rows", text).unwrap();
+    assert_eq!(
+        names_lines(&cases),
+        [(Some("first"), 2), (None, 3), (None, 3), (Some("last"), 5)]
+    );
+    let inputs: Vec<_> = cases.iter().map(|case| case.input().as_str()).collect();
+    assert_eq!(inputs, ["a, ]", "é 😀", "b", "\"c\","]);
+}
+
+#[test]
+fn values_are_deserialized() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Fragment {
+        Separator,
+        Plain(String),
+    }
+    type Expected = Result<(String, Fragment), String>;
+
+    let text = r#"[
+        {"input": "///", "expected": {"Ok": ["", "Separator"]}},
+        {"input": "a/b", "expected": {"Ok": ["/b", {"Plain": "a"}]}},
+        {"input": "", "expected": {"Err": "empty input"}}
+    ]"#;
+    let cases: Vec<Case<String, Expected>> =
+        common::load("values.json", text, cases_from_json).unwrap();
+    let expected: Vec<_> = cases.iter().map(|case| case.expected()).collect();
+    assert_eq!(
+        expected,
+        [
+            &Ok((String::new(), Fragment::Separator)),
+            &Ok(("/b".to_owned(), Fragment::Plain("a".to_owned()))),
+            &Err("empty input".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn syntax_errors_name_the_line_and_column() {
+    let err = load(
+        "syntax",
+        "[\n  {\"input\": \"a\", \"expected\": 1},\n  {\"input\": \"b\" 2}\n]",
+    )
+    .unwrap_err();
+    assert_eq!(
+        (err.line(), err.column(), err.row()),
+        (Some(3), Some(17), None)
+    );
+    assert_eq!(err.message(), "expected `,` or `}`");
+
+    let err = load("garbage", "[] x").unwrap_err();
+    assert_eq!(err.message(), "trailing characters");
+    assert!(err.to_string().ends_with(":1:4: trailing characters"));
+
+    let err = load("object", "{}").unwrap_err();
+    assert_eq!(err.message(), "invalid type: map, expected a sequence");
+}
+
+#[test]
+fn row_errors_name_the_line_column_and_row() {
+    let err = load(
+        "row",
+        "[\n  {\"input\": \"a\", \"expected\": 1},\n  {\"input\": \"é\", \"expected\": 1.5}\n]",
+    )
+    .unwrap_err();
+    assert_eq!(
+        (err.line(), err.column(), err.row()),
+        (Some(3), Some(32), Some(2))
+    );
+    assert_eq!(
+        err.message(),
+        "invalid type: floating point `1.5`, expected i64"
+    );
+
+    let message = |text: &str| load("row", text).unwrap_err().message().to_owned();
+    assert_eq!(message(r#"[{"input": "a"}]"#), "missing field `expected`");
+    assert_eq!(
+        message(r#"[{"input": "a", "expected": 1, "input": "b"}]"#),
+        "duplicate field `input`"
+    );
+    assert!(message(r#"[{"input": "a", "expected": 1, "other": 2}]"#)
+        .starts_with("unknown field `other`"));
+    assert_eq!(
+        message("[1]"),
+        "invalid type: integer `1`, expected struct Row"
+    );
+}
+
+/// An array of arrays, as deep as its JSON.
+#[derive(Debug, Deserialize)]
+// only read through `Debug`
+#[allow(dead_code)]
+struct Nested(Vec<Nested>);
+
+#[test]
+fn deep_nesting_is_an_error() {
+    let text = format!(
+        "[\n  {{\"input\": \"a\", \"expected\": {}",
+        "[".repeat(5000)
+    );
+    let err = common::load("deep.json", &text, cases_from_json::<String, IgnoredAny>).unwrap_err();
+    assert_eq!(err.message(), "EOF while parsing a list");
+
+    let nested = |depth| {
+        let text = format!(
+            "[\n  {{\"input\": \"a\", \"expected\": {}{}}}]",
+            "[".repeat(depth),
+            "]".repeat(depth)
+        );
+        common::load("deep.json", &text, cases_from_json::<String, Nested>)
+    };
+    let err = nested(5000).unwrap_err();
+    assert_eq!((err.line(), err.row()), (Some(2), Some(1)));
+    assert_eq!(err.message(), "recursion limit exceeded");
+    // the row is the first level
+    assert!(nested(126).is_ok());
+    assert!(nested(127).is_err());
+}
+
+#[test]
+fn unreadable_files_are_errors() {
+    let err = cases_from_json::<String, i64>("tests/no_such_file.json").unwrap_err();
+    assert_eq!(err.file(), "tests/no_such_file.json");
+    assert_eq!(err.line(), None);
+}
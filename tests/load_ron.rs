@@ -0,0 +1,138 @@
+#![cfg(feature = "ron")]
+
+mod common;
+
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use tiny_test::{cases_from_ron, Case, LoadError};
+
+fn load(name: &str, text: &str) -> Result<Vec<Case<String, i64>>, LoadError> {
+    common::load(&format!("{}.ron", name), text, cases_from_ron)
+}
+
+/// The names and lines of `cases`.
+fn names_lines<I, E>(cases: &[Case<I, E>]) -> Vec<(Option<&str>, u32)> {
+    cases
+        .iter()
+        .map(|case| (case.name(), case.location().unwrap().line()))
+        .collect()
+}
+
+#[test]
+fn rows_are_located_at_their_line() {
+    let text = r##"#![enable(implicit_some)]
+[
+    // a comment with a `(` and `,`
+    (name: "first", input: "a, ]", expected: 1),
+    /* a /* nested */ comment, */ (input: r#"b")"#, expected: 2),
+
+    (
+        name: "last",
+        input: "\"c\",",
+        expected: 3,
+    ),
+]
+"##;
+    let cases = load("rows", text).unwrap();
+    assert_eq!(
+        names_lines(&cases),
+        [(Some("first"), 4), (None, 5), (Some("last"), 7)]
+    );
+    let inputs: Vec<_> = cases.iter().map(|case| case.input().as_str()).collect();
+    assert_eq!(inputs, ["a, ]", "b\")", "\"c\","]);
+}
+
+#[test]
+fn names_need_no_extension() {
+    let text = r#"[
+  (name: "first", input: "a", expected: 1),
+  (name: Some("second"), input: "b", expected: 2),
+]"#;
+    let cases = load("names", text).unwrap();
+    assert_eq!(
+        names_lines(&cases),
+        [(Some("first"), 2), (Some("second"), 3)]
+    );
+}
+
+#[test]
+fn rows_on_one_line_are_told_apart_by_column() {
+    let text =
+        "[(input: \"a\", expected: 1), (input: \"b\", expected: 2), (input: \"c\", expected: x)]";
+    let err = load("one_line", text).unwrap_err();
+    assert_eq!((err.line(), err.row()), (Some(1), Some(3)));
+}
+
+#[test]
+fn values_are_deserialized() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Fragment {
+        Separator,
+        Plain(String),
+    }
+
+    let text = r#"[
+        (input: "///", expected: Ok(("", Separator))),
+        (input: "a/b", expected: Ok(("/b", Plain("a")))),
+        (input: "", expected: Err("empty input")),
+    ]"#;
+    type Expected = Result<(String, Fragment), String>;
+
+    let cases: Vec<Case<String, Expected>> =
+        common::load("values.ron", text, cases_from_ron).unwrap();
+    let expected: Vec<_> = cases.iter().map(|case| case.expected()).collect();
+    assert_eq!(
+        expected,
+        [
+            &Ok((String::new(), Fragment::Separator)),
+            &Ok(("/b".to_owned(), Fragment::Plain("a".to_owned()))),
+            &Err("empty input".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn syntax_errors_name_the_line_column_and_row() {
+    let err = load(
+        "syntax",
+        "[\n  (input: \"a\", expected: 1),\n  (input: \"b\" expected: 2),\n]",
+    )
+    .unwrap_err();
+    assert_eq!(err.line(), Some(3));
+    assert!(err.column().is_some());
+    assert_eq!(err.row(), Some(2));
+    assert!(err.to_string().starts_with(&format!("{}:3:", err.file())));
+}
+
+#[test]
+fn invalid_rows_are_errors() {
+    let err = load("unknown", "[\n  (input: \"a\", expected: 1, extra: 0),\n]").unwrap_err();
+    assert_eq!((err.line(), err.row()), (Some(2), Some(1)));
+    assert!(err.message().contains("extra"), "{}", err);
+
+    let err = load(
+        "missing",
+        "[\n  (input: \"a\", expected: 1),\n  (input: \"b\"),\n]",
+    )
+    .unwrap_err();
+    assert_eq!((err.line(), err.row()), (Some(3), Some(2)));
+    assert!(err.message().contains("expected"), "{}", err);
+
+    let err = load("type", "[(input: \"a\", expected: \"1\")]").unwrap_err();
+    assert_eq!((err.line(), err.row()), (Some(1), Some(1)));
+}
+
+#[test]
+fn deep_nesting_is_an_error() {
+    let text = format!("[\n  (input: \"a\", expected: {}1", "[".repeat(5000));
+    let err = common::load("deep.ron", &text, cases_from_ron::<String, IgnoredAny>).unwrap_err();
+    assert_eq!((err.line(), err.row()), (Some(2), Some(1)));
+}
+
+#[test]
+fn empty_and_unreadable_files() {
+    assert!(load("empty", "[]").unwrap().is_empty());
+    let err = cases_from_ron::<String, i64>("tests/no_such_file.ron").unwrap_err();
+    assert_eq!(err.file(), "tests/no_such_file.ron");
+    assert_eq!((err.line(), err.row()), (None, None));
+}
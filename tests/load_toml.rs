@@ -0,0 +1,91 @@
+#![cfg(feature = "toml")]
+
+mod common;
+
+use serde::de::IgnoredAny;
+use tiny_test::{cases_from_toml, Case, LoadError};
+
+fn load(name: &str, text: &str) -> Result<Vec<Case<String, i64>>, LoadError> {
+    common::load(&format!("{}.toml", name), text, cases_from_toml)
+}
+
+#[test]
+fn cases_are_located_at_their_header() {
+    let text = r#"# the first case
+[[case]]
+name = "first"
+input = "a"
+expected = 1
+
+[[case]]
+input = """
+[[case]]
+"""
+expected = 2
+"#;
+    let cases = load("cases", text).unwrap();
+    let located: Vec<_> = cases
+        .iter()
+        .map(|case| (case.name(), case.location().unwrap().line()))
+        .collect();
+    assert_eq!(located, [(Some("first"), 2), (None, 7)]);
+    assert_eq!(cases[1].input(), "[[case]]\n");
+}
+
+#[test]
+fn values_are_deserialized() {
+    let text = r#"
+[[case]]
+input = "1"
+expected = { Ok = [1, 2] }
+
+[[case]]
+input = "2"
+expected.Err = "no"
+"#;
+    type Expected = Result<(u8, u8), String>;
+
+    let cases: Vec<Case<String, Expected>> =
+        common::load("values.toml", text, cases_from_toml).unwrap();
+    let expected: Vec<_> = cases.iter().map(|case| case.expected()).collect();
+    assert_eq!(expected, [&Ok((1, 2)), &Err("no".to_owned())]);
+}
+
+#[test]
+fn syntax_errors_name_the_line_and_column() {
+    let err = load("syntax", "[[case]]\ninput = \"a\"\nexpected = = 1\n").unwrap_err();
+    assert_eq!(err.line(), Some(3));
+    assert!(err.column().is_some());
+    assert!(err.to_string().starts_with(&format!("{}:3:", err.file())));
+}
+
+#[test]
+fn invalid_cases_are_errors() {
+    let err = load(
+        "unknown",
+        "[[case]]\ninput = \"a\"\nexpected = 1\n\n[[case]]\ninput = \"b\"\nextra = 0\nexpected = 2\n",
+    )
+    .unwrap_err();
+    assert!(err.line() >= Some(5), "{}", err);
+    assert!(err.message().contains("extra"), "{}", err);
+
+    let err = load("missing", "[[case]]\ninput = \"a\"\n").unwrap_err();
+    assert!(err.message().contains("expected"), "{}", err);
+
+    let err = load("type", "[[case]]\ninput = \"a\"\nexpected = \"1\"\n").unwrap_err();
+    assert_eq!(err.line(), Some(3));
+}
+
+#[test]
+fn deep_nesting_is_an_error() {
+    let text = format!("[[case]]\ninput = \"a\"\nexpected = {}1", "[".repeat(5000));
+    let err = common::load("deep.toml", &text, cases_from_toml::<String, IgnoredAny>).unwrap_err();
+    assert_eq!(err.line(), Some(3));
+}
+
+#[test]
+fn unreadable_files_are_errors() {
+    let err = cases_from_toml::<String, i64>("tests/no_such_file.toml").unwrap_err();
+    assert_eq!(err.file(), "tests/no_such_file.toml");
+    assert_eq!(err.line(), None);
+}
@@ -0,0 +1,66 @@
+use std::time::{Duration, Instant};
+
+use tiny_test::matchers::{matches_regex, Matcher};
+
+fn is_match(pattern: &str, text: &str) -> bool {
+    matches_regex(pattern).check(text).is_ok()
+}
+
+#[test]
+fn literals_classes_and_anchors() {
+    assert!(is_match("b.d", "abcde"));
+    assert!(!is_match("b.d", "b\nd"));
+    assert!(is_match(r"^\d+-\w+\s?$", "12-ab_c "));
+    assert!(!is_match(r"^\d+$", "12a"));
+    assert!(is_match("[^a-c]", "abcd"));
+    assert!(!is_match("[^a-c]", "abc"));
+    assert!(is_match(r"[\d_]x", "_x"));
+    assert!(is_match("^$", ""));
+    assert!(!is_match("a^", "a"));
+}
+
+#[test]
+fn alternation_and_quantifiers() {
+    assert!(is_match("^(cat|dog)s?$", "dogs"));
+    assert!(!is_match("^(cat|dog)s?$", "cats!"));
+    assert!(is_match("^(?:ab)+$", "ababab"));
+    assert!(is_match("^a{2,3}$", "aaa"));
+    assert!(!is_match("^a{2,3}$", "aaaa"));
+    assert!(is_match("^a{2}$", "aa"));
+    assert!(is_match("^a{2,}$", "aaaaa"));
+    assert!(!is_match("^a{2,}$", "a"));
+    assert!(is_match("^a+?b*?$", "aabb"));
+    assert!(is_match("^(a*)*$", "aaa"));
+    assert!(is_match("^(a|)+b$", "aab"));
+}
+
+#[test]
+fn long_inputs_do_not_overflow_the_stack() {
+    let text = "a".repeat(100_000);
+    assert!(!is_match("a*b", &text));
+    assert!(is_match("^a*$", &text));
+    assert!(is_match("^(a|b)+$", &text));
+    assert!(is_match(".*a$", &text));
+}
+
+#[test]
+fn nested_quantifiers_run_in_linear_time() {
+    let text = format!("{}!", "a".repeat(10_000));
+    let start = Instant::now();
+    assert!(!is_match("^(a+)+$", &text));
+    assert!(!is_match("^(a|aa)*$", &text));
+    assert!(!is_match("^(a*)*b", &text));
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+#[should_panic(expected = "invalid regex \"a{1,x}\" at 1: invalid quantifier")]
+fn malformed_upper_bounds_are_invalid() {
+    matches_regex("a{1,x}");
+}
+
+#[test]
+#[should_panic(expected = "quantifier too large")]
+fn large_counts_are_invalid() {
+    matches_regex("a{100000}");
+}
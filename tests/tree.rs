@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use tiny_test::matchers::{debug_pattern, fields, Matcher};
+use tiny_test::{CaseFailure, FailReport};
+
+/// The report of a single failure with the `expected` value and `result`.
+fn report<T: Debug>(expected: T, result: T) -> String {
+    FailReport::from(vec![CaseFailure::new(1, (), expected, result)]).to_string()
+}
+
+/// The differing paths listed in the report of `expected` and `result`.
+fn paths<T: Debug>(expected: T, result: T) -> Vec<String> {
+    let report = report(expected, result);
+    report
+        .split_once("\tdiffering paths:\n")
+        .map(|(_, paths)| {
+            paths
+                .lines()
+                .filter_map(|line| line.strip_prefix("\t  "))
+                .map(str::to_owned)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// the fields are only read through `Debug`
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Span {
+    start: usize,
+    end: usize,
+}
+
+#[allow(dead_code)]
+#[derive(Debug)]
+enum Token {
+    Ident(&'static str),
+    Number { value: i64, span: Span },
+    Eof,
+}
+
+#[test]
+fn struct_and_enum_paths() {
+    assert_eq!(
+        paths(
+            Token::Number {
+                value: 1,
+                span: Span { start: 0, end: 1 }
+            },
+            Token::Number {
+                value: 2,
+                span: Span { start: 0, end: 3 }
+            },
+        ),
+        [".value: expected 1, got 2", ".span.end: expected 1, got 3"]
+    );
+    assert_eq!(
+        paths(Token::Ident("a"), Token::Ident("b")),
+        [r#".0: expected "a", got "b""#]
+    );
+}
+
+#[test]
+fn different_variants_differ_as_a_whole() {
+    assert_eq!(paths(Token::Ident("a"), Token::Eof), Vec::<String>::new());
+    assert_eq!(
+        paths(vec![Token::Ident("a")], vec![Token::Eof]),
+        [r#"[0]: expected Ident("a"), got Eof"#]
+    );
+}
+
+#[test]
+fn list_and_map_paths() {
+    assert_eq!(
+        paths(vec![1, 2, 3], vec![1, 5]),
+        ["[1]: expected 2, got 5", ": expected 3 elements, got 2"]
+    );
+    let map = |entries: &[(&'static str, i32)]| entries.iter().copied().collect::<BTreeMap<_, _>>();
+    assert_eq!(
+        paths(map(&[("a", 1), ("b", 2)]), map(&[("a", 3), ("c", 2)])),
+        [
+            r#"["a"]: expected 1, got 3"#,
+            r#"["b"]: expected 2, key is missing"#,
+            r#"["c"]: unexpected key, got 2"#,
+        ]
+    );
+}
+
+#[test]
+fn strings_with_delimiters_are_atoms() {
+    assert_eq!(
+        paths(("a, [b]", "x"), ("a, [b]", "y \"}\"")),
+        [r#".1: expected "x", got "y \"}\"""#]
+    );
+    assert_eq!(paths(('(', 1), ('(', 2)), [".1: expected 1, got 2"]);
+}
+
+#[test]
+fn unparsable_representations_list_no_paths() {
+    struct Opaque(&'static str);
+    impl Debug for Opaque {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+    let report = report(Opaque("<a (b>"), Opaque("<a (c>"));
+    assert!(!report.contains("differing paths"), "{}", report);
+}
+
+#[test]
+fn long_path_lists_are_truncated() {
+    let paths = paths([0; 10], [1; 10]);
+    assert_eq!(paths.len(), 9);
+    assert_eq!(paths[8], "and 2 more");
+}
+
+#[test]
+fn patterns_match_wildcards_and_rest() {
+    let span = Span { start: 2, end: 5 };
+    assert_eq!(
+        debug_pattern("Span { start: _, end: 5 }").check(&span),
+        Ok(())
+    );
+    assert_eq!(debug_pattern("Span { end: 5, .. }").check(&span), Ok(()));
+    assert_eq!(debug_pattern("[1, _, ..]").check(&vec![1, 2, 3]), Ok(()));
+    assert!(debug_pattern("[1, _]").check(&vec![1, 2, 3]).is_err());
+    assert!(debug_pattern("Span { end: 5 }").check(&span).is_err());
+}
+
+#[test]
+#[should_panic(expected = "invalid debug pattern")]
+fn unbalanced_patterns_panic() {
+    debug_pattern("Span { start: 1");
+}
+
+#[test]
+fn field_paths_resolve_tuples_lists_and_maps() {
+    let value = (
+        vec![Span { start: 0, end: 1 }],
+        BTreeMap::from([("key", 'k')]),
+    );
+    assert_eq!(
+        fields()
+            .field(".0[0].end", 1)
+            .field(r#".1["key"]"#, 'k')
+            .check(&value),
+        Ok(())
+    );
+    assert_eq!(
+        fields().field(".0[1].end", 1).check(&value),
+        Err(".0[1].end: expected 1, .0[1] is missing".to_owned())
+    );
+    assert_eq!(
+        fields().field(r#".1["other"]"#, 'k').check(&value),
+        Err(r#".1["other"]: expected 'k', .1["other"] is missing"#.to_owned())
+    );
+}
@@ -0,0 +1,536 @@
+//! Derive macros of `tiny-test`, enabled by its `derive` feature.
+
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+
+/// Derives `tiny_test::TinyDiff`, comparing a struct or enum field-by-field.
+///
+/// - `#[tiny(ignore)]` excludes a field from the comparison.
+/// - `#[tiny(compare_with = "path::to::fn")]` compares a field with a function `fn(&T, &T) -> bool`,
+///   the field type must implement `Debug`.
+/// - Type parameters are required to implement `TinyDiff`.
+///
+/// # Examples
+/// ```rust
+/// use tiny_test::{Difference, TinyDiff};
+///
+/// #[derive(Debug, TinyDiff)]
+/// struct Token {
+///     kind: Kind,
+///     #[tiny(compare_with = "same_len")]
+///     text: String,
+///     #[tiny(ignore)]
+///     hash: u64,
+/// }
+///
+/// #[derive(Debug, PartialEq, TinyDiff)]
+/// enum Kind {
+///     Ident,
+///     Number { radix: u32 },
+/// }
+///
+/// fn same_len(expected: &String, result: &String) -> bool {
+///     expected.len() == result.len()
+/// }
+///
+/// let expected = Token { kind: Kind::Number { radix: 10 }, text: "12".to_owned(), hash: 1 };
+/// let result = Token { kind: Kind::Number { radix: 16 }, text: "0x12".to_owned(), hash: 2 };
+///
+/// assert_eq!(
+///     expected.differences(&result),
+///     vec![
+///         Difference::new(".kind.radix", "expected 10, got 16"),
+///         Difference::new(".text", "expected \"12\", got \"0x12\""),
+///     ]
+/// );
+/// ```
+///
+/// `collect_fails!` decides the verdict of results implementing `TinyDiff` by their differences,
+/// so results differing only in ignored fields pass, and lists the differences in the report:
+/// ```rust
+/// use tiny_test::{collect_fails, TinyDiff};
+///
+/// #[derive(Debug, PartialEq, TinyDiff)]
+/// struct Spanned<T>(T, #[tiny(ignore)] usize)
+/// where
+///     T: PartialEq;
+///
+/// let fails = collect_fails!(
+///     &str,
+///     Option<Spanned<char>>,
+///     vec![("ab", Some(Spanned('a', 0))), ("", None)].into_iter(),
+///     |input: &&str| input.chars().next().map(|c| Spanned(c.to_ascii_uppercase(), 0))
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(fails.fails()[0].differences()[0].path(), ".0.0");
+///
+/// #[derive(Debug, PartialEq, TinyDiff)]
+/// struct Tok {
+///     r#type: u8,
+///     #[tiny(ignore)]
+///     hash: u64,
+/// }
+///
+/// let fails = collect_fails!(
+///     u8,
+///     Tok,
+///     vec![(1, Tok { r#type: 1, hash: 1 }), (2, Tok { r#type: 3, hash: 1 })].into_iter(),
+///     |ty: &u8| Tok { r#type: *ty, hash: 99 }
+/// );
+///
+/// assert_eq!(fails.len(), 1);
+/// assert_eq!(fails.fails()[0].case_id(), 2);
+/// assert!(fails.to_string().contains("differing paths:\n\t  .type: expected 3, got 2\n"));
+/// assert!(!fails.to_string().contains(".hash"));
+/// ```
+#[proc_macro_derive(TinyDiff, attributes(tiny))]
+pub fn derive_tiny_diff(input: TokenStream) -> TokenStream {
+    let code = match Input::parse(input) {
+        Ok(input) => input.expand(),
+        Err(message) => format!("::core::compile_error!({:?});", message),
+    };
+    code.parse().expect("generated code is valid")
+}
+
+/// The parsed item the derive is applied to.
+struct Input {
+    name: String,
+    generics: Vec<GenericParam>,
+    where_clause: String,
+    data: Data,
+}
+
+struct GenericParam {
+    /// The name of a type parameter, `None` for lifetimes and const parameters.
+    ty: Option<String>,
+    /// The name used as generic argument, such as `'a`, `T` or `N`.
+    name: String,
+    /// The declaration without default, such as `T: Clone` or `const N: usize`.
+    declaration: String,
+}
+
+enum Data {
+    Struct(Fields),
+    Enum(Vec<Variant>),
+}
+
+enum Fields {
+    Named(Vec<Field>),
+    Unnamed(Vec<Field>),
+    Unit,
+}
+
+struct Field {
+    /// The identifier of a named field, or the index of an unnamed field.
+    member: String,
+    ignore: bool,
+    compare_with: Option<String>,
+}
+
+struct Variant {
+    name: String,
+    fields: Fields,
+}
+
+type Result<T> = std::result::Result<T, String>;
+
+fn is_punct(token: Option<&TokenTree>, c: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == c)
+}
+
+fn is_ident(token: Option<&TokenTree>, ident: &str) -> bool {
+    matches!(token, Some(TokenTree::Ident(i)) if i.to_string() == ident)
+}
+
+fn to_string(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
+
+/// Splits `tokens` at commas outside of angle brackets.
+fn split_commas(tokens: Vec<TokenTree>) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![Vec::new()];
+    let mut depth = 0usize;
+    let mut arrow = false;
+    for token in tokens {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' && depth == 0 => {
+                parts.push(Vec::new());
+                continue;
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '<' => depth += 1,
+            // the `>` of `->` does not close an angle bracket
+            TokenTree::Punct(punct) if punct.as_char() == '>' && !arrow => {
+                depth = depth.saturating_sub(1)
+            }
+            _ => {}
+        }
+        arrow = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '-' && punct.spacing() == Spacing::Joint);
+        parts.last_mut().expect("parts is never empty").push(token);
+    }
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Skips outer attributes, returning the options of `#[tiny(...)]` attributes.
+fn attributes(tokens: &[TokenTree], pos: &mut usize) -> Result<Vec<Vec<TokenTree>>> {
+    let mut options = Vec::new();
+    while is_punct(tokens.get(*pos), '#') {
+        let Some(TokenTree::Group(group)) = tokens.get(*pos + 1) else {
+            return Err("expected attribute".to_owned());
+        };
+        let content: Vec<TokenTree> = group.stream().into_iter().collect();
+        if is_ident(content.first(), "tiny") {
+            match content.get(1) {
+                Some(TokenTree::Group(args)) if args.delimiter() == Delimiter::Parenthesis => {
+                    options.extend(split_commas(args.stream().into_iter().collect()))
+                }
+                _ => return Err("expected `#[tiny(...)]`".to_owned()),
+            }
+        }
+        *pos += 2;
+    }
+    Ok(options)
+}
+
+/// Skips `pub`, `pub(crate)` and similar visibilities.
+fn visibility(tokens: &[TokenTree], pos: &mut usize) {
+    if is_ident(tokens.get(*pos), "pub") {
+        *pos += 1;
+        if matches!(tokens.get(*pos), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis)
+        {
+            *pos += 1;
+        }
+    }
+}
+
+fn ident(tokens: &[TokenTree], pos: &mut usize) -> Result<String> {
+    match tokens.get(*pos) {
+        Some(TokenTree::Ident(ident)) => {
+            *pos += 1;
+            Ok(ident.to_string())
+        }
+        _ => Err("expected identifier".to_owned()),
+    }
+}
+
+impl Input {
+    fn parse(input: TokenStream) -> Result<Self> {
+        let tokens: Vec<TokenTree> = input.into_iter().collect();
+        let mut pos = 0;
+        attributes(&tokens, &mut pos)?;
+        visibility(&tokens, &mut pos);
+        let kind = ident(&tokens, &mut pos)?;
+        let name = ident(&tokens, &mut pos)?;
+        let generics = Self::generics(&tokens, &mut pos)?;
+
+        let mut where_clause = Vec::new();
+        let mut body = None;
+        while let Some(token) = tokens.get(pos) {
+            pos += 1;
+            match token {
+                TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                    body = Some(group.clone());
+                    break;
+                }
+                // the fields of a tuple struct precede its where clause
+                TokenTree::Group(group)
+                    if group.delimiter() == Delimiter::Parenthesis && where_clause.is_empty() =>
+                {
+                    body = Some(group.clone())
+                }
+                TokenTree::Punct(punct) if punct.as_char() == ';' => break,
+                _ => where_clause.push(token.clone()),
+            }
+        }
+        let where_clause = match where_clause.split_first() {
+            Some((first, predicates)) if is_ident(Some(first), "where") => to_string(predicates),
+            Some(_) => return Err("expected where clause".to_owned()),
+            None => String::new(),
+        };
+
+        let data = match (kind.as_str(), body) {
+            ("struct", Some(body)) if body.delimiter() == Delimiter::Brace => {
+                Data::Struct(Fields::named(body.stream())?)
+            }
+            ("struct", Some(body)) => Data::Struct(Fields::unnamed(body.stream())?),
+            ("struct", None) => Data::Struct(Fields::Unit),
+            ("enum", Some(body)) => Data::Enum(Variant::parse_all(body.stream())?),
+            _ => return Err("`TinyDiff` can only be derived for structs and enums".to_owned()),
+        };
+        Ok(Self {
+            name,
+            generics,
+            where_clause,
+            data,
+        })
+    }
+
+    fn generics(tokens: &[TokenTree], pos: &mut usize) -> Result<Vec<GenericParam>> {
+        if !is_punct(tokens.get(*pos), '<') {
+            return Ok(Vec::new());
+        }
+        let start = *pos + 1;
+        let mut depth = 0usize;
+        let mut end = None;
+        for (index, token) in tokens.iter().enumerate().skip(*pos) {
+            match token {
+                TokenTree::Punct(punct) if punct.as_char() == '<' => depth += 1,
+                TokenTree::Punct(punct)
+                    if punct.as_char() == '>' && !is_punct(tokens.get(index - 1), '-') =>
+                {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(index);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let end = end.ok_or_else(|| "unclosed generics".to_owned())?;
+        *pos = end + 1;
+        split_commas(tokens[start..end].to_vec())
+            .into_iter()
+            .map(|param| {
+                // the default of a parameter is not repeated in the impl
+                let declaration = match param.iter().position(|token| is_punct(Some(token), '=')) {
+                    Some(index) => &param[..index],
+                    None => &param[..],
+                };
+                let declaration_text = to_string(declaration);
+                if is_punct(param.first(), '\'') {
+                    let name = to_string(&param[..2]);
+                    Ok(GenericParam {
+                        ty: None,
+                        name,
+                        declaration: declaration_text,
+                    })
+                } else if is_ident(param.first(), "const") {
+                    let name = ident(&param, &mut 1)?;
+                    Ok(GenericParam {
+                        ty: None,
+                        name,
+                        declaration: declaration_text,
+                    })
+                } else {
+                    let name = ident(&param, &mut 0)?;
+                    Ok(GenericParam {
+                        ty: Some(name.clone()),
+                        name,
+                        declaration: declaration_text,
+                    })
+                }
+            })
+            .collect()
+    }
+
+    fn expand(&self) -> String {
+        let impl_generics = self
+            .generics
+            .iter()
+            .map(|param| param.declaration.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let type_generics = self
+            .generics
+            .iter()
+            .map(|param| param.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut predicates: Vec<String> = self
+            .generics
+            .iter()
+            .filter_map(|param| param.ty.as_ref())
+            .map(|ty| format!("{}: ::tiny_test::TinyDiff", ty))
+            .collect();
+        if !self.where_clause.is_empty() {
+            predicates.insert(0, self.where_clause.trim_end_matches(',').to_owned());
+        }
+        let where_clause = if predicates.is_empty() {
+            String::new()
+        } else {
+            format!("where {}", predicates.join(", "))
+        };
+
+        let body = match &self.data {
+            Data::Struct(fields) => {
+                let (pattern, statements) = fields.expand("Self");
+                format!(
+                    "let {} = self; let {} = result; {}",
+                    pattern.replace("{prefix}", "__expected_"),
+                    pattern.replace("{prefix}", "__result_"),
+                    statements
+                )
+            }
+            Data::Enum(variants) if variants.is_empty() => "match *self {}".to_owned(),
+            Data::Enum(variants) => {
+                let arms: String = variants
+                    .iter()
+                    .map(|variant| {
+                        let (pattern, statements) =
+                            variant.fields.expand(&format!("Self::{}", variant.name));
+                        format!(
+                            "({}, {}) => {{ {} }}",
+                            pattern.replace("{prefix}", "__expected_"),
+                            pattern.replace("{prefix}", "__result_"),
+                            statements
+                        )
+                    })
+                    .collect();
+                format!(
+                    "match (self, result) {{ {} #[allow(unreachable_patterns)] _ => \
+                     differences.push(::tiny_test::Difference::mismatch(path, self, result)), }}",
+                    arms
+                )
+            }
+        };
+
+        format!(
+            "#[automatically_derived] \
+             impl<{impl_generics}> ::tiny_test::TinyDiff for {name}<{type_generics}> {where_clause} {{ \
+                 #[allow(unused_variables)] \
+                 fn tiny_diff(&self, result: &Self, path: &str, \
+                     differences: &mut ::std::vec::Vec<::tiny_test::Difference>) {{ {body} }} \
+             }}",
+            impl_generics = impl_generics,
+            name = self.name,
+            type_generics = type_generics,
+            where_clause = where_clause,
+            body = body,
+        )
+    }
+}
+
+impl Fields {
+    fn named(stream: TokenStream) -> Result<Self> {
+        split_commas(stream.into_iter().collect())
+            .into_iter()
+            .map(|tokens| {
+                let mut pos = 0;
+                let options = attributes(&tokens, &mut pos)?;
+                visibility(&tokens, &mut pos);
+                let member = ident(&tokens, &mut pos)?;
+                Field::new(member, options)
+            })
+            .collect::<Result<_>>()
+            .map(Fields::Named)
+    }
+
+    fn unnamed(stream: TokenStream) -> Result<Self> {
+        split_commas(stream.into_iter().collect())
+            .into_iter()
+            .enumerate()
+            .map(|(index, tokens)| {
+                let options = attributes(&tokens, &mut 0)?;
+                Field::new(index.to_string(), options)
+            })
+            .collect::<Result<_>>()
+            .map(Fields::Unnamed)
+    }
+
+    /// Returns the pattern destructuring `path` into bindings `{prefix}{member}`, and the statements
+    /// comparing the bindings of the expected value and the result.
+    fn expand(&self, path: &str) -> (String, String) {
+        let fields = match self {
+            Fields::Named(fields) | Fields::Unnamed(fields) => fields,
+            Fields::Unit => return (path.to_owned(), String::new()),
+        };
+        let bindings: Vec<String> = fields
+            .iter()
+            .map(|field| format!("{}: {{prefix}}{}", field.member, field.name()))
+            .collect();
+        let pattern = format!("{} {{ {} }}", path, bindings.join(", "));
+        let statements = fields.iter().map(Field::expand).collect();
+        (pattern, statements)
+    }
+}
+
+impl Field {
+    fn new(member: String, options: Vec<Vec<TokenTree>>) -> Result<Self> {
+        let mut field = Field {
+            member,
+            ignore: false,
+            compare_with: None,
+        };
+        for option in options {
+            match option.as_slice() {
+                [TokenTree::Ident(ident)] if ident.to_string() == "ignore" => field.ignore = true,
+                [TokenTree::Ident(ident), TokenTree::Punct(eq), TokenTree::Literal(literal)]
+                    if ident.to_string() == "compare_with" && eq.as_char() == '=' =>
+                {
+                    let literal = literal.to_string();
+                    match literal
+                        .strip_prefix('"')
+                        .and_then(|path| path.strip_suffix('"'))
+                    {
+                        Some(path) => field.compare_with = Some(path.to_owned()),
+                        None => return Err("expected `compare_with = \"path::to::fn\"`".to_owned()),
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                    "unknown option `{}`, expected `ignore` or `compare_with = \"path::to::fn\"`",
+                    to_string(&option)
+                ))
+                }
+            }
+        }
+        Ok(field)
+    }
+
+    /// The member without the `r#` of a raw identifier, as shown in paths and appended to bindings.
+    fn name(&self) -> &str {
+        self.member.trim_start_matches("r#")
+    }
+
+    fn expand(&self) -> String {
+        let path = format!("&::std::format!(\"{{}}.{}\", path)", self.name());
+        let (expected, result) = (
+            format!("__expected_{}", self.name()),
+            format!("__result_{}", self.name()),
+        );
+        if self.ignore {
+            String::new()
+        } else if let Some(compare_with) = &self.compare_with {
+            format!(
+                "if !{compare_with}({expected}, {result}) {{ \
+                     differences.push(::tiny_test::Difference::mismatch({path}, {expected}, {result})); \
+                 }}",
+                compare_with = compare_with,
+                expected = expected,
+                result = result,
+                path = path,
+            )
+        } else {
+            format!(
+                "::tiny_test::TinyDiff::tiny_diff({}, {}, {}, differences);",
+                expected, result, path
+            )
+        }
+    }
+}
+
+impl Variant {
+    fn parse_all(stream: TokenStream) -> Result<Vec<Self>> {
+        split_commas(stream.into_iter().collect())
+            .into_iter()
+            .map(|tokens| {
+                let mut pos = 0;
+                attributes(&tokens, &mut pos)?;
+                let name = ident(&tokens, &mut pos)?;
+                let fields = match tokens.get(pos) {
+                    Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                        Fields::named(group.stream())?
+                    }
+                    Some(TokenTree::Group(group))
+                        if group.delimiter() == Delimiter::Parenthesis =>
+                    {
+                        Fields::unnamed(group.stream())?
+                    }
+                    _ => Fields::Unit,
+                };
+                Ok(Variant { name, fields })
+            })
+            .collect()
+    }
+}